@@ -1,13 +1,35 @@
 use omc_galaxy::{Orchestrator, PlanetInfoMap, utils::ExplorerInfoMap};
-use ratatui::widgets::TableState;
+use ratatui::widgets::{ListState, TableState};
 use std::{
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
 
-use crate::{game_state::GameState, tui_loggers::LogBuffer};
+use crate::{
+    console::{Console, ConsoleCommand},
+    editor::GalaxyEditor,
+    galaxy_file::{GalaxyFile, ParseError, PlanetType},
+    game_state::GameState,
+    generator::{self, GeneratorForm},
+    keymap::KeyMap,
+    notifications::Notifications,
+    palette::CommandPalette,
+    planet_table::PlanetView,
+    scenario::{Scenario, ScenarioAction},
+    stats::GameStats,
+    theme::{self, Theme, Themes},
+    tui_loggers::{Entity, LogBuffer},
+    ui::{self, ClickAreas, Tab},
+};
 use omc_galaxy::settings;
 
+/// Time between confirming the resume and the game actually running again
+pub const RESUME_COUNTDOWN: Duration = Duration::from_secs(3);
+/// Change of the sunray probability for the fine and coarse controls
+pub const SUNRAY_FINE_STEP: i32 = 1;
+pub const SUNRAY_COARSE_STEP: i32 = 10;
+
 pub struct App {
     //State of the game
     pub(crate) gamestate: GameState,
@@ -27,16 +49,78 @@ pub struct App {
     //Game logs
     pub log_entries: Arc<LogBuffer>,
 
-    //UI planet selector variables
+    //Keys bound to each action
+    pub(crate) keymap: KeyMap,
+    //Colour themes, built-in and from the theme file
+    pub(crate) themes: Themes,
+    //Text markers instead of colour-only cues, and ASCII instead of box drawing
+    pub(crate) accessible: bool,
+    //Plain text description of the selection, shown until a key is pressed
+    pub(crate) description: Option<String>,
+
+    //UI planet selector variables: the selected planet ID, the table only keeps its scroll
+    pub(crate) selected_planet: Option<u32>,
     pub(crate) table_state: TableState,
+    //Order and filter of the planets table
+    pub(crate) planet_view: PlanetView,
+    //UI explorer selector, only changed with the mouse
+    pub(crate) explorer_table_state: TableState,
+    //Where the clickable widgets were drawn, for the mouse
+    pub(crate) click_areas: ClickAreas,
+
+    //View shown below the header
+    pub(crate) tab: Tab,
 
     //UI log overlay toggle
     pub show_log_overlay: bool,
+    //Lines the log overlay is scrolled back from the latest one
+    pub(crate) log_scroll: u16,
+    //Planet or explorer the log overlay and tab are restricted to
+    pub(crate) log_filter: Option<Entity>,
+    //Sunray probability entry popup, with the digits typed so far
+    pub(crate) sunray_entry: Option<String>,
+    //Help popup, and instructions panel shrunk to a hint line
+    pub(crate) show_help: bool,
+    pub(crate) instructions_collapsed: bool,
+    //Toasts and history of the important game events, and the history popup
+    pub(crate) notifications: Notifications,
+    pub(crate) show_notifications: bool,
+    //Command palette, present only while open
+    pub(crate) palette: Option<CommandPalette>,
+    //Scripting console, created the first time it is opened
+    pub(crate) console: Option<Console>,
+    pub(crate) show_console: bool,
+    //Start of the countdown before leaving the pause, if requested
+    pub(crate) resume_countdown: Option<Instant>,
+
+    //Start screen galaxy file picker
+    pub(crate) galaxy_dir: String,
+    pub(crate) galaxy_files: Vec<String>,
+    pub(crate) galaxy_file_state: ListState,
+    pub(crate) galaxy_preview: Option<(GalaxyFile, Vec<ParseError>)>,
+    //Galaxy generator popup, present only while open
+    pub(crate) generator_form: Option<GeneratorForm>,
+    //Scenario run by the active loop, and the scenario files of the start screen
+    pub(crate) scenario: Option<Scenario>,
+    pub(crate) scenario_files: Vec<String>,
+
+    //Galaxy file the game was started from, for what the orchestrator doesn't report
+    pub(crate) galaxy_file: Option<GalaxyFile>,
+
+    //Statistics for the end screen
+    pub(crate) stats: GameStats,
+    //Feedback of the last end screen action (report export)
+    pub(crate) end_status: Option<String>,
+
+    //Galaxy file editor, present only while editing
+    pub(crate) editor: Option<GalaxyEditor>,
+    //Last error that prevented the game from starting
+    pub(crate) start_error: Option<String>,
 }
 
 impl App {
     pub fn new(orchestrator: Orchestrator, log_buffer: Arc<LogBuffer>) -> Self {
-        Self {
+        let mut app = Self {
             gamestate: GameState::WaitingStart,
             planets_info: orchestrator.get_planets_info(),
             explorers_info: orchestrator.get_explorer_states(),
@@ -50,10 +134,51 @@ impl App {
             frame_rate: Duration::from_millis(33), // UI fluida a 30 FPS
             log_entries: log_buffer,
 
+            keymap: KeyMap::load(),
+            themes: Themes::load(),
+            // Without colours the markers are the only cues left
+            accessible: theme::no_color() || std::env::var_os("ACCESSIBLE").is_some(),
+            description: None,
+
+            selected_planet: None,
             table_state: TableState::default(),
+            planet_view: PlanetView::default(),
+            explorer_table_state: TableState::default(),
+            click_areas: ClickAreas::default(),
+
+            tab: Tab::default(),
 
             show_log_overlay: false,
-        }
+            log_scroll: 0,
+            log_filter: None,
+            sunray_entry: None,
+            show_help: false,
+            instructions_collapsed: false,
+            notifications: Notifications::load(),
+            show_notifications: false,
+            palette: None,
+            console: None,
+            show_console: false,
+            resume_countdown: None,
+
+            galaxy_dir: Self::galaxy_dir(),
+            galaxy_files: Vec::new(),
+            galaxy_file_state: ListState::default(),
+            galaxy_preview: None,
+            generator_form: None,
+            scenario: None,
+            scenario_files: Vec::new(),
+
+            galaxy_file: None,
+            stats: GameStats::default(),
+            end_status: None,
+
+            editor: None,
+            start_error: None,
+        };
+        app.refresh_galaxy_files();
+        app.refresh_scenario_files();
+        app
     }
 
     pub fn get_game_state(&self) -> GameState {
@@ -64,108 +189,707 @@ impl App {
         self.gamestate = state;
     }
 
-    pub fn initialize_by_file(&mut self) -> Result<(), String> {
+    /// Colours of the theme in use
+    pub(crate) fn theme(&self) -> Theme {
+        self.themes.current()
+    }
+
+    pub(crate) fn cycle_theme(&mut self) {
+        self.themes.cycle();
+        log::info!("Colour theme: {}", self.themes.current_name());
+    }
+
+    pub(crate) fn toggle_accessibility(&mut self) {
+        self.accessible = !self.accessible;
+        log::info!(
+            "Accessibility mode {}",
+            if self.accessible { "on" } else { "off" }
+        );
+    }
+
+    /// Shows and logs a linear description of the selected planet and explorer
+    pub(crate) fn describe_selection(&mut self) {
+        let description = self.selection_description();
+        log::info!("{}", description);
+        self.description = Some(description);
+    }
+
+    /// Path of the default galaxy file, read from INPUT_FILE
+    pub fn input_file_path() -> Result<String, String> {
         // Load env
         dotenv::dotenv().ok();
 
         // Give the absolute path for the init file
         let file_path =
             std::env::var("INPUT_FILE").map_err(|_| "Set INPUT_FILE in .env or env vars")?;
+        Ok(file_path.trim().to_string())
+    }
+
+    /// Directory listed by the start screen: GALAXY_DIR, or the folder of INPUT_FILE
+    fn galaxy_dir() -> String {
+        dotenv::dotenv().ok();
 
+        if let Ok(dir) = std::env::var("GALAXY_DIR") {
+            return dir.trim().to_string();
+        }
+        Self::input_file_path()
+            .ok()
+            .and_then(|file| Path::new(&file).parent().map(|p| p.display().to_string()))
+            .filter(|dir| !dir.is_empty())
+            .unwrap_or_else(|| ".".to_string())
+    }
+
+    pub fn initialize_by_file(&mut self, file_path: &str) -> Result<(), String> {
+        // Only the orchestrator knows its file format, so its error is the one shown
         self.orchestrator
-            .initialize_galaxy_by_file(file_path.as_str().trim())
-            .map_err(|_| "Failed to initialize galaxy")?;
+            .initialize_galaxy_by_file(file_path)
+            .map_err(|e| format!("Failed to initialize galaxy: {}", e))?;
+        // Planet types are only known if the file is also in the editor's format
+        let (galaxy_file, errors) = GalaxyFile::load(file_path);
+        self.galaxy_file = errors.is_empty().then_some(galaxy_file);
+        self.get_game_info();
+        self.explorers_info = self.orchestrator.get_explorer_states();
         Ok(())
     }
 
-    pub(crate) fn set_sunray_increment(&mut self) {
-        settings::set_sunray_probability(self.probability_sunray + 1);
+    /// Leaves the pause, the next tick is a full tick away
+    pub(crate) fn resume(&mut self) {
+        self.resume_countdown = None;
+        self.last_tick = Instant::now();
+        self.set_game_state(GameState::Running);
     }
-    pub(crate) fn set_sunray_decrement(&mut self) {
-        settings::set_sunray_probability(self.probability_sunray - 1);
+
+    /// Goes back to the start screen with a fresh orchestrator, ready for a new game
+    pub(crate) fn restart(&mut self) -> Result<(), String> {
+        self.orchestrator = Orchestrator::new()?;
+        self.planets_info = self.orchestrator.get_planets_info();
+        self.explorers_info = self.orchestrator.get_explorer_states();
+        self.galaxy_topology = self.orchestrator.get_galaxy_topology();
+        self.stats = GameStats::default();
+        self.galaxy_file = None;
+        self.log_filter = None;
+        if let Some(scenario) = self.scenario.as_mut() {
+            scenario.rewind();
+        }
+        self.end_status = None;
+        self.selected_planet = None;
+        self.table_state = TableState::default();
+        self.planet_view = PlanetView::default();
+        self.explorer_table_state.select(None);
+        self.sunray_entry = None;
+        self.notifications.history.clear();
+        self.show_notifications = false;
+        self.tab = Tab::default();
+        self.set_game_state(GameState::WaitingStart);
+        Ok(())
+    }
+
+    /// Writes the end screen report to a text file in the current directory
+    ///
+    /// The log follows the report, one entry per line with its fields as `key=value`.
+    pub(crate) fn export_report(&mut self) {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let file_path = format!("galaxy_report_{}.txt", timestamp);
+
+        let mut report = self.stats.report(&self.explorers_info);
+        report.push_str("\nLog:\n");
+        for entry in self.log_entries.logs.lock().unwrap().iter() {
+            report.push_str(&format!("  {}\n", entry));
+        }
+
+        self.end_status = Some(match std::fs::write(&file_path, report) {
+            Ok(()) => format!("Report saved to {}", file_path),
+            Err(e) => format!("Cannot write {}: {}", file_path, e),
+        });
+    }
+
+    /// Opens the galaxy editor on the galaxy selected in the start screen
+    pub(crate) fn open_editor(&mut self) -> Result<(), String> {
+        let file_path = self
+            .selected_galaxy_file()
+            .ok_or("No galaxy file selected")?;
+        self.editor = Some(GalaxyEditor::open(&file_path));
+        self.set_game_state(GameState::Editing);
+        Ok(())
+    }
+
+    /// Leaves the editor and goes back to the start screen
+    pub(crate) fn close_editor(&mut self) {
+        self.editor = None;
+        self.set_game_state(GameState::WaitingStart);
+        // The file may have been changed
+        self.refresh_galaxy_preview();
+    }
+
+    /// Scrolls the log overlay by `lines`, negative is up towards the older entries
+    ///
+    /// `log_scroll` counts the lines back from the latest one, so the logs keep
+    /// following the game until they are scrolled up.
+    pub(crate) fn scroll_log(&mut self, lines: i32) {
+        // Keeps at least the oldest entry in view
+        let max = self.log_entries.logs.lock().unwrap().len() as i32 - 1;
+        self.log_scroll = (self.log_scroll as i32 - lines).clamp(0, max.max(0)) as u16;
+    }
+
+    /// Sets the sunray probability, clamped to 0-100, logging the tick it takes effect
+    pub(crate) fn set_sunray_probability(&mut self, probability: i32) {
+        let probability = probability.clamp(0, 100) as u32;
+        if probability == self.probability_sunray {
+            return;
+        }
+        settings::set_sunray_probability(probability);
+        // The cached value is only refreshed every tick, quick steps must add up
+        self.probability_sunray = probability;
+        log::info!(
+            "Sunray probability set to {}%, effective from tick {}",
+            probability,
+            self.stats.tick + 1
+        );
+    }
+
+    pub(crate) fn change_sunray_probability(&mut self, delta: i32) {
+        self.set_sunray_probability(self.probability_sunray as i32 + delta);
+    }
+
+    /// Applies the value typed in the sunray entry popup and closes it
+    pub(crate) fn submit_sunray_entry(&mut self) {
+        if let Some(value) = self.sunray_entry.take().and_then(|v| v.parse().ok()) {
+            self.set_sunray_probability(value);
+        }
     }
 }
 
-// Selector for the planet table
+// Events sent on top of the random stream, `tick` is the one they are logged with
 impl App {
-    pub(crate) fn increment_id_selector(&mut self) {
-        let n = self.planets_info.len();
-        if n == 0 {
+    /// Sends a sunray or an asteroid, the orchestrator picks which with the sunray probability
+    pub(crate) fn send_random_event(&mut self, tick: u64) -> Result<(), String> {
+        self.orchestrator.send_sunray_or_asteroid()?;
+        self.stats.random_events += 1;
+        log::info!("Extra random event sent at tick {}", tick);
+        Ok(())
+    }
+}
+
+// Scheduled scenario
+impl App {
+    /// Directory searched for `*.scenario.toml` files: SCENARIO_DIR, or the galaxy directory
+    fn scenario_dir(&self) -> String {
+        std::env::var("SCENARIO_DIR")
+            .map(|dir| dir.trim().to_string())
+            .unwrap_or_else(|_| self.galaxy_dir.clone())
+    }
+
+    pub(crate) fn refresh_scenario_files(&mut self) {
+        let mut files: Vec<String> = std::fs::read_dir(self.scenario_dir())
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path().display().to_string())
+                    .filter(|path| path.ends_with(".scenario.toml"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort();
+        self.scenario_files = files;
+    }
+
+    /// Moves to the next scenario file, going through "no scenario" after the last one
+    pub(crate) fn select_next_scenario(&mut self) {
+        let current = self
+            .scenario
+            .as_ref()
+            .and_then(|s| self.scenario_files.iter().position(|f| *f == s.path));
+        let next = match current {
+            Some(i) => self.scenario_files.get(i + 1),
+            None if self.scenario.is_some() => None,
+            None => self.scenario_files.first(),
+        };
+
+        self.scenario = None;
+        if let Some(file) = next.cloned() {
+            match Scenario::load(&file) {
+                Ok(scenario) => {
+                    self.start_error = None;
+                    self.scenario = Some(scenario);
+                }
+                Err(e) => self.start_error = Some(e),
+            }
+        }
+    }
+
+    /// Executes the scenario actions due at `tick`
+    ///
+    /// Failed actions are logged and skipped, the scenario goes on.
+    pub(crate) fn run_scenario_tick(&mut self, tick: u64) {
+        let Some(scenario) = self.scenario.as_mut() else {
             return;
+        };
+        for action in scenario.take_due(tick) {
+            log::info!("Scenario, tick {}: {}", tick, action);
+            if let Err(e) = self.perform_event(&action, tick) {
+                log::warn!("Scenario action `{}` failed: {}", action, e);
+            }
         }
+    }
 
-        let i = match self.table_state.selected() {
-            Some(i) => {
-                if i >= n - 1 {
-                    0
-                } else {
-                    i + 1
+    /// Performs an action of a scenario or of a console script during `tick`
+    pub(crate) fn perform_event(
+        &mut self,
+        action: &ScenarioAction,
+        tick: u64,
+    ) -> Result<(), String> {
+        match action {
+            ScenarioAction::SetSunray(probability) => {
+                self.set_sunray_probability(*probability as i32);
+            }
+            ScenarioAction::RandomEvent => self.send_random_event(tick)?,
+            ScenarioAction::Pause => {
+                if self.get_game_state() == GameState::Running {
+                    self.set_game_state(GameState::Paused);
                 }
             }
-            None => 0,
+        }
+        Ok(())
+    }
+}
+
+// Scripting console
+impl App {
+    pub(crate) fn open_console(&mut self) {
+        self.console.get_or_insert_with(Console::new);
+        self.show_console = true;
+    }
+
+    /// Runs the line typed in the console
+    pub(crate) fn submit_console(&mut self) {
+        let Some(mut console) = self.console.take() else {
+            return;
         };
+        let commands = console.submit(self);
+        self.console = Some(console);
+        self.run_console_commands(commands);
+    }
 
-        self.table_state.select(Some(i));
+    /// Runs the `on_tick` function of the console, if defined
+    pub(crate) fn run_console_hook(&mut self) {
+        let Some(mut console) = self.console.take() else {
+            return;
+        };
+        let commands = console.run_tick_hook(self);
+        self.console = Some(console);
+        self.run_console_commands(commands);
     }
 
+    /// Runs what a script asked for, after the last tick played
+    fn run_console_commands(&mut self, commands: Vec<ConsoleCommand>) {
+        let tick = self.stats.tick;
+        for command in commands {
+            match command {
+                ConsoleCommand::Event(action) => {
+                    if let Err(e) = self.perform_event(&action, tick) {
+                        log::warn!("Console action `{}` failed: {}", action, e);
+                    }
+                }
+                ConsoleCommand::SelectPlanet(id) => self.select_planet(id),
+            }
+        }
+    }
+}
+
+// Galaxy file picker of the start screen
+impl App {
+    /// Lists the `.txt` files of the galaxy directory, keeping INPUT_FILE selected if present
+    pub(crate) fn refresh_galaxy_files(&mut self) {
+        let mut files: Vec<String> = std::fs::read_dir(&self.galaxy_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.is_file() && path.extension().is_some_and(|ext| ext == "txt")
+                    })
+                    .map(|path| path.display().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort();
+
+        // INPUT_FILE is the default choice, even when it lives elsewhere
+        let default_file = Self::input_file_path().ok();
+        if let Some(file) = &default_file
+            && !files.iter().any(|f| Path::new(f) == Path::new(file))
+        {
+            files.insert(0, file.clone());
+        }
+
+        let selected = default_file
+            .and_then(|file| files.iter().position(|f| Path::new(f) == Path::new(&file)))
+            .or(if files.is_empty() { None } else { Some(0) });
+
+        self.galaxy_files = files;
+        self.galaxy_file_state.select(selected);
+        self.refresh_galaxy_preview();
+    }
+
+    pub(crate) fn selected_galaxy_file(&self) -> Option<String> {
+        self.galaxy_file_state
+            .selected()
+            .and_then(|i| self.galaxy_files.get(i))
+            .cloned()
+    }
+
+    pub(crate) fn select_galaxy_file(&mut self, i: usize) {
+        if i < self.galaxy_files.len() {
+            self.galaxy_file_state.select(Some(i));
+            self.refresh_galaxy_preview();
+        }
+    }
+
+    pub(crate) fn select_next_galaxy_file(&mut self) {
+        if !self.galaxy_files.is_empty() {
+            let i = self.galaxy_file_state.selected().map_or(0, |i| i + 1);
+            self.galaxy_file_state
+                .select(Some(i % self.galaxy_files.len()));
+            self.refresh_galaxy_preview();
+        }
+    }
+
+    pub(crate) fn select_previous_galaxy_file(&mut self) {
+        let n = self.galaxy_files.len();
+        if n > 0 {
+            let i = self.galaxy_file_state.selected().map_or(0, |i| i + n - 1);
+            self.galaxy_file_state.select(Some(i % n));
+            self.refresh_galaxy_preview();
+        }
+    }
+
+    /// Generates a galaxy from the generator form and saves it in the galaxy directory
+    ///
+    /// On success the form is closed and the new file selected, otherwise
+    /// the error is shown in the form.
+    pub(crate) fn generate_galaxy(&mut self) {
+        let Some(form) = self.generator_form.as_mut() else {
+            return;
+        };
+
+        let file_path = Path::new(&self.galaxy_dir)
+            .join(form.config.file_name())
+            .display()
+            .to_string();
+        if let Err(e) = generator::generate_file(&form.config, &file_path) {
+            form.error = Some(e);
+            return;
+        }
+
+        self.generator_form = None;
+        self.refresh_galaxy_files();
+        if let Some(i) = self.galaxy_files.iter().position(|f| *f == file_path) {
+            self.galaxy_file_state.select(Some(i));
+            self.refresh_galaxy_preview();
+        }
+    }
+
+    /// Parses the selected file once, so the preview isn't read from disk every frame
+    fn refresh_galaxy_preview(&mut self) {
+        self.galaxy_preview = self
+            .selected_galaxy_file()
+            .map(|file| GalaxyFile::load(&file));
+    }
+}
+
+// Selector for the planet table
+impl App {
+    /// Selects the planet in the next row of the planets table, the first after the last one
+    pub(crate) fn increment_id_selector(&mut self) {
+        let rows = self.planet_rows();
+        if rows.is_empty() {
+            return;
+        }
+
+        let i = match self.selected_planet_row(&rows) {
+            Some(i) => (i + 1) % rows.len(),
+            None => 0,
+        };
+        self.selected_planet = Some(rows[i]);
+    }
+
+    /// Selects the planet in the previous row of the planets table, the last before the first one
     pub(crate) fn decrement_id_selector(&mut self) {
-        let n = self.planets_info.len();
-        if n == 0 {
+        let rows = self.planet_rows();
+        if rows.is_empty() {
             return;
         }
 
-        let i = match self.table_state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    n - 1
-                } else {
-                    i - 1
-                }
-            }
-            None => n - 1,
+        let i = match self.selected_planet_row(&rows) {
+            Some(i) => (i + rows.len() - 1) % rows.len(),
+            None => rows.len() - 1,
         };
+        self.selected_planet = Some(rows[i]);
+    }
 
-        self.table_state.select(Some(i));
+    /// Selects the planet with the given ID, if the galaxy has one
+    pub(crate) fn select_planet(&mut self, id: u32) {
+        if self.planets_info.get_info(id).is_some() {
+            self.selected_planet = Some(id);
+        }
     }
-    pub(crate) fn get_rocket_of_selected_planet(&self) -> String {
-        match self.table_state.selected() {
-            Some(selected) => {
-                if self.planets_info.get_info(selected as u32).unwrap().rocket {
-                    "AVAILABLE".to_string()
-                } else {
-                    "NOT PRESENT".to_string()
+
+    pub(crate) fn selected_planet_id(&self) -> Option<u32> {
+        self.selected_planet
+    }
+
+    /// Row of the selected planet among `rows`, `None` if it is filtered out
+    pub(crate) fn selected_planet_row(&self, rows: &[u32]) -> Option<usize> {
+        let selected = self.selected_planet?;
+        rows.iter().position(|id| *id == selected)
+    }
+
+    /// Forgets the selected planet once the galaxy doesn't have it anymore
+    pub(crate) fn drop_missing_planet_selection(&mut self) {
+        if let Some(id) = self.selected_planet
+            && self.planets_info.get_info(id).is_none()
+        {
+            log::info!("Planet {} is gone, selection cleared", id);
+            self.selected_planet = None;
+        }
+    }
+
+    /// IDs of the planets in the rows of the planets table, sorted and filtered
+    pub(crate) fn planet_rows(&self) -> Vec<u32> {
+        self.planet_view
+            .rows(&self.planets_info, &self.galaxy_topology)
+    }
+
+    pub(crate) fn cycle_planet_sort(&mut self) {
+        self.planet_view.cycle_sort();
+        log::info!("Planets sorted by {}", self.planet_view.sort.title());
+    }
+
+    pub(crate) fn reverse_planet_sort(&mut self) {
+        self.planet_view.reverse();
+    }
+
+    pub(crate) fn cycle_planet_filter(&mut self) {
+        self.planet_view.cycle_filter(self.selected_planet);
+        log::info!("Planets shown: {}", self.planet_view.filter.title());
+    }
+
+    /// Whether two planets are linked, false for planets missing from the topology
+    pub(crate) fn linked(&self, a: u32, b: u32) -> bool {
+        self.galaxy_topology
+            .get(a as usize)
+            .and_then(|row| row.get(b as usize))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Type of a planet, from the galaxy file the game was started from
+    pub(crate) fn planet_type(&self, id: u32) -> Option<PlanetType> {
+        self.galaxy_file
+            .as_ref()?
+            .planet(id)
+            .map(|planet| planet.planet_type)
+    }
+
+    /// Latest `n` log lines about a planet or explorer, oldest first
+    pub(crate) fn entity_log_lines(&self, entity: Entity, n: usize) -> Vec<(log::Level, String)> {
+        let logs = self.log_entries.logs.lock().unwrap();
+        let mut lines: Vec<_> = logs
+            .iter()
+            .rev()
+            .filter(|entry| entry.concerns(entity))
+            .take(n)
+            .map(|entry| (entry.level, entry.message.clone()))
+            .collect();
+        lines.reverse();
+        lines
+    }
+
+    /// Filters the log to the selected planet, then to the selected explorer, then shows it all
+    pub(crate) fn cycle_log_filter(&mut self) {
+        let planet = self.selected_planet.map(Entity::Planet);
+        let explorer = self
+            .explorer_table_state
+            .selected()
+            .and_then(|i| self.explorers_info.iter().nth(i))
+            .map(|(id, _)| Entity::Explorer(*id));
+
+        self.log_filter = match self.log_filter {
+            None => planet.or(explorer),
+            Some(Entity::Planet(_)) => explorer,
+            Some(Entity::Explorer(_)) => None,
+        };
+        self.log_scroll = 0;
+        match self.log_filter {
+            Some(entity) => log::info!("Log filtered to {}", entity),
+            None => log::info!("Log filter cleared"),
+        }
+    }
+
+    /// Links of the galaxy topology, each pair once
+    pub(crate) fn topology_links(&self) -> Vec<(u32, u32)> {
+        let mut links = Vec::new();
+        for (a, neighbours) in self.galaxy_topology.iter().enumerate() {
+            for (b, linked) in neighbours.iter().enumerate().skip(a + 1) {
+                if *linked {
+                    links.push((a as u32, b as u32));
                 }
             }
+        }
+        links
+    }
+
+    pub(crate) fn get_rocket_of_selected_planet(&self) -> String {
+        match self
+            .selected_planet
+            .and_then(|id| self.planets_info.get_info(id))
+        {
+            Some(planet) if planet.rocket => "AVAILABLE".to_string(),
+            Some(_) => "NOT PRESENT".to_string(),
             None => "None".to_string(),
         }
     }
     pub(crate) fn get_cells_info_selected_planet(&self) -> String {
-        match self.table_state.selected() {
-            Some(selected) => {
-                let planet = self.planets_info.get_info(selected as u32).unwrap();
-                format!(
-                    "{} out of {}",
-                    planet.charged_cells_count,
-                    planet.energy_cells.len()
-                )
-            }
-            None => format!("None"),
+        match self
+            .selected_planet
+            .and_then(|id| self.planets_info.get_info(id))
+        {
+            Some(planet) => format!(
+                "{} out of {}",
+                planet.charged_cells_count,
+                planet.energy_cells.len()
+            ),
+            None => "None".to_string(),
         }
     }
     pub(crate) fn get_id_selected_planet(&self) -> String {
-        match self.table_state.selected() {
-            Some(selected) => selected.to_string(),
-            None => format!("None"),
+        match self.selected_planet {
+            Some(id) => id.to_string(),
+            None => "None".to_string(),
         }
     }
     pub(crate) fn get_name_selected_planet(&self) -> String {
-        if let Some(planet) = self.table_state.selected() {
-            format!(
-                "{:?}",
-                self.planets_info.get_info(planet as u32).unwrap().name
-            )
+        match self
+            .selected_planet
+            .and_then(|id| self.planets_info.get_info(id))
+        {
+            Some(planet) => format!("{:?}", planet.name),
+            None => "None".to_string(),
+        }
+    }
+}
+
+// Selector for the explorer table
+impl App {
+    pub(crate) fn select_next_explorer(&mut self) {
+        let n = self.explorers_info.len();
+        if n > 0 {
+            let i = self.explorer_table_state.selected().map_or(0, |i| i + 1);
+            self.explorer_table_state.select(Some(i % n));
+        }
+    }
+
+    pub(crate) fn select_previous_explorer(&mut self) {
+        let n = self.explorers_info.len();
+        if n > 0 {
+            let i = self
+                .explorer_table_state
+                .selected()
+                .map_or(0, |i| i + n - 1);
+            self.explorer_table_state.select(Some(i % n));
+        }
+    }
+
+    /// One sentence per selected entity, readable without seeing the tables
+    pub(crate) fn selection_description(&self) -> String {
+        let mut sentences = Vec::new();
+
+        let planet = self
+            .selected_planet_id()
+            .and_then(|id| self.planets_info.iter().find(|(planet, _)| **planet == id));
+        if let Some((id, info)) = planet {
+            let status = ui::status_text(&info.status).to_lowercase();
+            let neighbours = self.neighbours_of(*id);
+            let explorers: Vec<String> = self
+                .explorers_info
+                .iter()
+                .filter(|(_, explorer)| explorer.current_planet_id == *id)
+                .map(|(explorer, _)| explorer.to_string())
+                .collect();
+            sentences.push(format!(
+                "Planet {} ({:?}) is {}, {} of {} energy cells charged, {}. Linked to {}. Explorers on it: {}.",
+                id,
+                info.name,
+                status,
+                info.charged_cells_count,
+                info.energy_cells.len(),
+                if info.rocket {
+                    "rocket available"
+                } else {
+                    "no rocket"
+                },
+                list_or_none(&neighbours, "planets ", "no planet"),
+                list_or_none(&explorers, "", "none"),
+            ));
+        }
+
+        let explorer = self
+            .explorer_table_state
+            .selected()
+            .and_then(|i| self.explorers_info.iter().nth(i));
+        if let Some((id, info)) = explorer {
+            let status = ui::status_text(&info.status).to_lowercase();
+            sentences.push(format!(
+                "Explorer {} is {}, on planet {}, which is linked to {}.",
+                id,
+                status,
+                info.current_planet_id,
+                list_or_none(
+                    &self.neighbours_of(info.current_planet_id),
+                    "planets ",
+                    "no planet"
+                ),
+            ));
+        }
+
+        if sentences.is_empty() {
+            "Nothing selected. Select a planet or an explorer first.".to_string()
         } else {
-            "None".to_string()
+            sentences.join(" ")
         }
     }
+
+    /// IDs of the planets linked to the given one
+    fn neighbours_of(&self, id: u32) -> Vec<String> {
+        self.galaxy_topology
+            .get(id as usize)
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, linked)| **linked)
+                    .map(|(neighbour, _)| neighbour.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get_selected_explorer(&self) -> String {
+        self.explorer_table_state
+            .selected()
+            .and_then(|i| self.explorers_info.iter().nth(i))
+            .map(|(id, info)| format!("{} on planet {}", id, info.current_planet_id))
+            .unwrap_or_else(|| "None".to_string())
+    }
+}
+
+/// `items` joined with commas after `prefix`, or `none` if empty
+fn list_or_none(items: &[String], prefix: &str, none: &str) -> String {
+    if items.is_empty() {
+        none.to_string()
+    } else {
+        format!("{}{}", prefix, items.join(", "))
+    }
 }
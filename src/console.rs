@@ -0,0 +1,321 @@
+use std::{cell::RefCell, rc::Rc};
+
+use omc_galaxy::Status;
+use rhai::{AST, Array, Dynamic, Engine, INT, Map, Scope};
+
+use crate::app::App;
+use crate::scenario::ScenarioAction;
+use crate::tui_loggers::FieldValue;
+
+/// Lines of output kept by the console
+const MAX_OUTPUT: usize = 200;
+/// Stops runaway scripts (e.g. `loop {}`) before they freeze the UI
+const MAX_OPERATIONS: u64 = 200_000;
+/// Nesting allowed at the top level and in functions, the debug build
+/// defaults are too low for a closure inside `reduce` in a function
+const MAX_EXPR_DEPTHS: (usize, usize) = (64, 32);
+
+/// Something a script asked the game to do, performed once the script is done
+#[derive(Clone, Debug)]
+pub enum ConsoleCommand {
+    Event(ScenarioAction),
+    SelectPlanet(u32),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OutputKind {
+    Input,
+    Output,
+    Error,
+}
+
+/// Rhai console, opened with `:`
+///
+/// Each line is evaluated with the game state available as constants
+/// (`tick`, `sunray`, `planets`, `explorers`, `topology`, `logs`) and a few
+/// functions to act on the game (`random_event()`, `set_sunray(p)`,
+/// `pause()`, `select_planet(id)`). Like scenarios, scripts can only send the
+/// events the orchestrator picks, with the sunray probability.
+/// Functions defined in the console are kept, and `on_tick(tick)` is called
+/// after every tick of the game, e.g. (from `console.example.rhai`)
+///
+/// ```rhai
+#[doc = include_str!("../console.example.rhai")]
+/// ```
+pub struct Console {
+    engine: Engine,
+    scope: Scope<'static>,
+    /// Functions defined so far
+    functions: AST,
+    commands: Rc<RefCell<Vec<ConsoleCommand>>>,
+    printed: Rc<RefCell<Vec<String>>>,
+    pub output: Vec<(OutputKind, String)>,
+    pub input: String,
+    history: Vec<String>,
+    history_index: Option<usize>,
+    /// Set when `on_tick` failed, so the error isn't repeated every tick
+    hook_failed: bool,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        let commands = Rc::new(RefCell::new(Vec::new()));
+        let printed = Rc::new(RefCell::new(Vec::new()));
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_expr_depths(MAX_EXPR_DEPTHS.0, MAX_EXPR_DEPTHS.1);
+
+        let out = Rc::clone(&printed);
+        engine.on_print(move |text| out.borrow_mut().push(text.to_string()));
+        let out = Rc::clone(&printed);
+        engine.on_debug(move |text, _, _| out.borrow_mut().push(text.to_string()));
+
+        let queue = |action: fn(u32) -> ConsoleCommand| {
+            let commands = Rc::clone(&commands);
+            move |id: INT| -> Result<(), Box<rhai::EvalAltResult>> {
+                let id = u32::try_from(id).map_err(|_| format!("invalid id {}", id))?;
+                commands.borrow_mut().push(action(id));
+                Ok(())
+            }
+        };
+        engine.register_fn(
+            "set_sunray",
+            queue(|p| ConsoleCommand::Event(ScenarioAction::SetSunray(p.min(100)))),
+        );
+        engine.register_fn("select_planet", queue(ConsoleCommand::SelectPlanet));
+        for (name, action) in [
+            ("random_event", ScenarioAction::RandomEvent),
+            ("pause", ScenarioAction::Pause),
+        ] {
+            let commands = Rc::clone(&commands);
+            engine.register_fn(name, move || {
+                commands
+                    .borrow_mut()
+                    .push(ConsoleCommand::Event(action.clone()));
+            });
+        }
+
+        Self {
+            engine,
+            scope: Scope::new(),
+            functions: AST::empty(),
+            commands,
+            printed,
+            output: vec![(
+                OutputKind::Output,
+                "Rhai console, try `planets`, `random_event()` or define fn on_tick(t)".to_string(),
+            )],
+            input: String::new(),
+            history: Vec::new(),
+            history_index: None,
+            hook_failed: false,
+        }
+    }
+
+    fn push_output(&mut self, kind: OutputKind, text: String) {
+        self.output
+            .extend(text.lines().map(|line| (kind, line.to_string())));
+        let excess = self.output.len().saturating_sub(MAX_OUTPUT);
+        self.output.drain(..excess);
+    }
+
+    /// Moves through the previous inputs, `-1` is older
+    pub fn recall(&mut self, direction: i32) {
+        if self.history.is_empty() {
+            return;
+        }
+        let last = self.history.len() - 1;
+        let index = match (self.history_index, direction < 0) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.history_index = index;
+        self.input = index.map(|i| self.history[i].clone()).unwrap_or_default();
+    }
+
+    /// Publishes the game state to the scripts
+    fn update_scope(&mut self, app: &App) {
+        let planets: Array = app
+            .planets_info
+            .iter()
+            .map(|(id, info)| {
+                let mut planet = Map::new();
+                planet.insert("id".into(), (*id as INT).into());
+                planet.insert("name".into(), format!("{:?}", info.name).into());
+                planet.insert("rocket".into(), info.rocket.into());
+                planet.insert("charged".into(), (info.charged_cells_count as INT).into());
+                planet.insert("cells".into(), (info.energy_cells.len() as INT).into());
+                planet.insert(
+                    "alive".into(),
+                    (!matches!(info.status, Status::Dead)).into(),
+                );
+                planet.into()
+            })
+            .collect();
+        let explorers: Array = app
+            .explorers_info
+            .iter()
+            .map(|(id, info)| {
+                let mut explorer = Map::new();
+                explorer.insert("id".into(), (*id as INT).into());
+                explorer.insert("planet".into(), (info.current_planet_id as INT).into());
+                explorer.insert(
+                    "alive".into(),
+                    (!matches!(info.status, Status::Dead)).into(),
+                );
+                explorer.into()
+            })
+            .collect();
+        let topology: Array = app
+            .galaxy_topology
+            .iter()
+            .map(|row| {
+                let row: Array = row.iter().map(|linked| (*linked).into()).collect();
+                row.into()
+            })
+            .collect();
+
+        // Each entry has its `level` and `message`, plus one key per structured field
+        let logs: Array = app
+            .log_entries
+            .logs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                let mut log = Map::new();
+                for (key, value) in &entry.fields {
+                    let value: Dynamic = match value {
+                        FieldValue::Bool(value) => (*value).into(),
+                        FieldValue::Int(value) => (*value as INT).into(),
+                        FieldValue::Float(value) => (*value).into(),
+                        FieldValue::Str(value) => value.clone().into(),
+                    };
+                    log.insert(key.as_str().into(), value);
+                }
+                log.insert("level".into(), entry.level.as_str().into());
+                log.insert("message".into(), entry.message.clone().into());
+                log.into()
+            })
+            .collect();
+
+        self.scope.set_or_push("tick", app.stats.tick as INT);
+        self.scope
+            .set_or_push("sunray", app.probability_sunray as INT);
+        self.scope.set_or_push("planets", planets);
+        self.scope.set_or_push("explorers", explorers);
+        self.scope.set_or_push("topology", topology);
+        self.scope.set_or_push("logs", logs);
+    }
+
+    /// Evaluates the input line, keeping the functions it defines
+    ///
+    /// Returns the commands queued by the script.
+    pub fn submit(&mut self, app: &App) -> Vec<ConsoleCommand> {
+        let line = std::mem::take(&mut self.input);
+        self.history_index = None;
+        if line.trim().is_empty() {
+            return Vec::new();
+        }
+        self.history.push(line.clone());
+        self.push_output(OutputKind::Input, format!("> {}", line));
+        self.update_scope(app);
+
+        let result = self
+            .engine
+            .compile_with_scope(&self.scope, &line)
+            .map_err(|e| e.to_string())
+            .and_then(|ast| {
+                let ast = self.functions.merge(&ast);
+                let value = self
+                    .engine
+                    .eval_ast_with_scope::<Dynamic>(&mut self.scope, &ast)
+                    .map_err(|e| e.to_string())?;
+                self.functions = ast.clone_functions_only();
+                Ok(value)
+            });
+
+        self.flush_printed();
+        match result {
+            Ok(value) if !value.is_unit() => {
+                self.push_output(OutputKind::Output, value.to_string())
+            }
+            Ok(_) => {}
+            Err(e) => self.push_output(OutputKind::Error, e),
+        }
+        if line.contains("on_tick") {
+            self.hook_failed = false;
+        }
+        self.commands.take()
+    }
+
+    /// Calls `on_tick(tick)` if the console defined it
+    pub fn run_tick_hook(&mut self, app: &App) -> Vec<ConsoleCommand> {
+        let defined = self
+            .functions
+            .iter_functions()
+            .any(|f| f.name == "on_tick" && f.params.len() == 1);
+        if !defined || self.hook_failed {
+            return Vec::new();
+        }
+
+        self.update_scope(app);
+        let result = self.engine.call_fn::<Dynamic>(
+            &mut self.scope,
+            &self.functions,
+            "on_tick",
+            (app.stats.tick as INT,),
+        );
+        self.flush_printed();
+        if let Err(e) = result {
+            self.hook_failed = true;
+            self.push_output(OutputKind::Error, format!("on_tick disabled: {}", e));
+            log::warn!("Console on_tick failed at tick {}: {}", app.stats.tick, e);
+        }
+        self.commands.take()
+    }
+
+    fn flush_printed(&mut self) {
+        let printed = self.printed.take();
+        for text in printed {
+            self.push_output(OutputKind::Output, text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(id: INT, charged: INT, alive: bool) -> Dynamic {
+        let mut planet = Map::new();
+        planet.insert("id".into(), id.into());
+        planet.insert("charged".into(), charged.into());
+        planet.insert("alive".into(), alive.into());
+        planet.into()
+    }
+
+    #[test]
+    fn the_documented_example_runs() {
+        let mut console = Console::new();
+        let planets: Array = vec![planet(0, 2, true), planet(1, 5, false), planet(2, 4, true)];
+        console.scope.push("planets", planets);
+
+        let ast = console
+            .engine
+            .compile_with_scope(&console.scope, include_str!("../console.example.rhai"))
+            .unwrap();
+        for tick in [9, 10] {
+            console
+                .engine
+                .call_fn::<()>(&mut console.scope, &ast, "on_tick", (tick as INT,))
+                .unwrap();
+        }
+        let commands = console.commands.take();
+        assert!(matches!(commands[..], [ConsoleCommand::SelectPlanet(2)]));
+    }
+}
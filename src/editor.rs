@@ -0,0 +1,130 @@
+use crate::galaxy_file::{self, GalaxyFile, ParseError, PlanetType};
+
+/// State of the galaxy file editor screen
+pub struct GalaxyEditor {
+    /// Path the galaxy was loaded from and will be saved to
+    pub path: String,
+    pub galaxy: GalaxyFile,
+    /// Errors found the last time the file was read from disk
+    pub parse_errors: Vec<ParseError>,
+    /// Result of the last validation, refreshed after every edit
+    pub issues: Vec<String>,
+    /// Cursor in the adjacency grid, as indices into `galaxy.planets`
+    pub cursor_row: usize,
+    pub cursor_col: usize,
+    /// Unsaved changes
+    pub dirty: bool,
+    /// Feedback of the last action (save, reload...)
+    pub status: String,
+}
+
+impl GalaxyEditor {
+    pub fn open(path: &str) -> Self {
+        let mut editor = Self {
+            path: path.to_string(),
+            galaxy: GalaxyFile::default(),
+            parse_errors: Vec::new(),
+            issues: Vec::new(),
+            cursor_row: 0,
+            cursor_col: 0,
+            dirty: false,
+            status: String::new(),
+        };
+        editor.reload();
+        editor
+    }
+
+    /// Discards every change and reads the file again
+    pub fn reload(&mut self) {
+        let (galaxy, errors) = GalaxyFile::load(&self.path);
+        self.galaxy = galaxy;
+        self.parse_errors = errors;
+        self.dirty = false;
+        self.status = format!("Loaded {}", self.path);
+        self.clamp_cursor();
+        self.revalidate();
+    }
+
+    /// Writes the file and loads it back in the orchestrator, which has the last word on the format
+    pub fn save(&mut self) {
+        if let Err(e) = self.galaxy.save(&self.path) {
+            self.status = e;
+            return;
+        }
+        self.dirty = false;
+        self.parse_errors.clear();
+        self.status = match galaxy_file::load_in_orchestrator(&self.path) {
+            Ok(()) => format!("Saved {}", self.path),
+            Err(e) => format!(
+                "Saved {}, but the orchestrator rejects it: {}",
+                self.path, e
+            ),
+        };
+    }
+
+    fn revalidate(&mut self) {
+        self.issues = self.galaxy.validate();
+    }
+
+    fn edited(&mut self) {
+        self.dirty = true;
+        self.clamp_cursor();
+        self.revalidate();
+    }
+
+    fn clamp_cursor(&mut self) {
+        let last = self.galaxy.planets.len().saturating_sub(1);
+        self.cursor_row = self.cursor_row.min(last);
+        self.cursor_col = self.cursor_col.min(last);
+    }
+
+    /// ID of the planet on the cursor row
+    pub fn selected_planet(&self) -> Option<u32> {
+        self.galaxy.planets.get(self.cursor_row).map(|p| p.id)
+    }
+
+    pub fn move_cursor(&mut self, rows: isize, cols: isize) {
+        let n = self.galaxy.planets.len();
+        if n == 0 {
+            return;
+        }
+        self.cursor_row = (self.cursor_row as isize + rows).rem_euclid(n as isize) as usize;
+        self.cursor_col = (self.cursor_col as isize + cols).rem_euclid(n as isize) as usize;
+    }
+
+    pub fn add_planet(&mut self) {
+        let id = self.galaxy.add_planet(PlanetType::A);
+        self.cursor_row = self.galaxy.planets.len() - 1;
+        self.status = format!("Added planet {}", id);
+        self.edited();
+    }
+
+    pub fn remove_selected_planet(&mut self) {
+        if let Some(id) = self.selected_planet() {
+            self.galaxy.remove_planet(id);
+            self.status = format!("Removed planet {}", id);
+            self.edited();
+        }
+    }
+
+    pub fn cycle_selected_type(&mut self) {
+        if let Some(id) = self.selected_planet() {
+            self.galaxy.cycle_planet_type(id);
+            self.edited();
+        }
+    }
+
+    /// Toggles the link between the planets under the grid cursor
+    pub fn toggle_link_at_cursor(&mut self) {
+        let planets = &self.galaxy.planets;
+        if let (Some(a), Some(b)) = (planets.get(self.cursor_row), planets.get(self.cursor_col)) {
+            let (a, b) = (a.id, b.id);
+            if a == b {
+                self.status = "A planet cannot be linked to itself".to_string();
+                return;
+            }
+            self.galaxy.toggle_link(a, b);
+            self.edited();
+        }
+    }
+}
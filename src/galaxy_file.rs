@@ -0,0 +1,460 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+use omc_galaxy::Orchestrator;
+
+/// Planet types understood by the orchestrator
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlanetType {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl PlanetType {
+    /// Returns the following type, wrapping around (used by the editor)
+    pub fn next(self) -> Self {
+        match self {
+            PlanetType::A => PlanetType::B,
+            PlanetType::B => PlanetType::C,
+            PlanetType::C => PlanetType::D,
+            PlanetType::D => PlanetType::A,
+        }
+    }
+
+    /// Whether planets of this type can build a rocket to stop an asteroid
+    pub fn can_build_rockets(self) -> bool {
+        matches!(self, PlanetType::A | PlanetType::C)
+    }
+
+    /// How many kinds of basic resource planets of this type can generate
+    pub fn basic_resources(self) -> &'static str {
+        match self {
+            PlanetType::A | PlanetType::C => "1 kind",
+            PlanetType::B | PlanetType::D => "unbounded",
+        }
+    }
+
+    /// How many combination rules, turning resources into complex ones, the type allows
+    pub fn combination_rules(self) -> &'static str {
+        match self {
+            PlanetType::A | PlanetType::D => "none",
+            PlanetType::B => "1",
+            PlanetType::C => "up to 6",
+        }
+    }
+
+    fn parse(token: &str) -> Option<Self> {
+        match token.to_ascii_uppercase().as_str() {
+            "A" => Some(PlanetType::A),
+            "B" => Some(PlanetType::B),
+            "C" => Some(PlanetType::C),
+            "D" => Some(PlanetType::D),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PlanetType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A planet line of the galaxy file
+#[derive(Clone, Debug)]
+pub struct PlanetEntry {
+    pub id: u32,
+    pub planet_type: PlanetType,
+    pub neighbours: BTreeSet<u32>,
+}
+
+/// An explorer line of the galaxy file
+#[derive(Clone, Debug)]
+pub struct ExplorerEntry {
+    pub id: u32,
+    pub planet_id: u32,
+}
+
+/// Error found while parsing a galaxy file, with the (1-based) line it comes from
+#[derive(Clone, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// In-memory representation of a galaxy file in the editor's format
+///
+/// The format is the one of the editor and the generator, line based with
+/// `#` starting a comment:
+/// ```text
+/// planet <id> <type A-D> <neighbour>,<neighbour>,...
+/// explorer <id> <planet id>
+/// ```
+/// omc-galaxy doesn't expose its parser, so this one may read files
+/// differently: whatever is written is checked with `load_in_orchestrator`,
+/// and the game starts from whatever the orchestrator accepts.
+#[derive(Clone, Debug, Default)]
+pub struct GalaxyFile {
+    pub planets: Vec<PlanetEntry>,
+    pub explorers: Vec<ExplorerEntry>,
+}
+
+impl GalaxyFile {
+    /// Reads and parses a galaxy file from disk
+    ///
+    /// Always returns the planets and explorers that could be parsed, along with
+    /// every error found, so a broken file can still be fixed in the editor.
+    pub fn load(path: &str) -> (Self, Vec<ParseError>) {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) => (
+                Self::default(),
+                vec![ParseError {
+                    line: 0,
+                    message: format!("Cannot read {}: {}", path, e),
+                }],
+            ),
+        }
+    }
+
+    /// Parses the content of a galaxy file, collecting every error found
+    pub fn parse(content: &str) -> (Self, Vec<ParseError>) {
+        let mut galaxy = Self::default();
+        let mut errors = Vec::new();
+
+        for (index, raw_line) in content.lines().enumerate() {
+            let line = index + 1;
+            let text = raw_line.split('#').next().unwrap_or("").trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            let mut error = |message: String| errors.push(ParseError { line, message });
+            let tokens: Vec<&str> = text.split_whitespace().collect();
+
+            match tokens[0].to_ascii_lowercase().as_str() {
+                "planet" => {
+                    if tokens.len() < 3 {
+                        error("expected `planet <id> <type> <neighbours>`".to_string());
+                        continue;
+                    }
+                    let Ok(id) = tokens[1].parse::<u32>() else {
+                        error(format!("invalid planet id `{}`", tokens[1]));
+                        continue;
+                    };
+                    if galaxy.planet(id).is_some() {
+                        error(format!("planet {} is defined twice", id));
+                        continue;
+                    }
+                    let Some(planet_type) = PlanetType::parse(tokens[2]) else {
+                        error(format!(
+                            "unknown planet type `{}` (expected A-D)",
+                            tokens[2]
+                        ));
+                        continue;
+                    };
+
+                    let mut neighbours = BTreeSet::new();
+                    let list = tokens[3..].join("");
+                    for neighbour in list.split(',').filter(|n| !n.is_empty()) {
+                        match neighbour.parse::<u32>() {
+                            Ok(n) => {
+                                neighbours.insert(n);
+                            }
+                            Err(_) => error(format!("invalid neighbour id `{}`", neighbour)),
+                        }
+                    }
+
+                    galaxy.planets.push(PlanetEntry {
+                        id,
+                        planet_type,
+                        neighbours,
+                    });
+                }
+                "explorer" => {
+                    if tokens.len() != 3 {
+                        error("expected `explorer <id> <planet id>`".to_string());
+                        continue;
+                    }
+                    match (tokens[1].parse::<u32>(), tokens[2].parse::<u32>()) {
+                        (Ok(id), Ok(planet_id)) => {
+                            if galaxy.explorers.iter().any(|e| e.id == id) {
+                                error(format!("explorer {} is defined twice", id));
+                            } else {
+                                galaxy.explorers.push(ExplorerEntry { id, planet_id });
+                            }
+                        }
+                        _ => error("explorer and planet ids must be numbers".to_string()),
+                    }
+                }
+                other => error(format!("unknown entry `{}`", other)),
+            }
+        }
+
+        (galaxy, errors)
+    }
+
+    /// Serializes the galaxy back to the file format
+    pub fn to_file_string(&self) -> String {
+        let mut out = String::from("# Galaxy file\n");
+        for planet in &self.planets {
+            let neighbours: Vec<String> = planet.neighbours.iter().map(|n| n.to_string()).collect();
+            out.push_str(&format!(
+                "planet {} {} {}\n",
+                planet.id,
+                planet.planet_type,
+                neighbours.join(",")
+            ));
+        }
+        for explorer in &self.explorers {
+            out.push_str(&format!(
+                "explorer {} {}\n",
+                explorer.id, explorer.planet_id
+            ));
+        }
+        out
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.to_file_string())
+            .map_err(|e| format!("Cannot write {}: {}", path, e))
+    }
+
+    pub fn planet(&self, id: u32) -> Option<&PlanetEntry> {
+        self.planets.iter().find(|p| p.id == id)
+    }
+
+    /// Undirected links between planets, each listed once as (lower id, higher id)
+    pub fn links(&self) -> Vec<(u32, u32)> {
+        let mut links = BTreeSet::new();
+        for planet in &self.planets {
+            for n in &planet.neighbours {
+                links.insert((planet.id.min(*n), planet.id.max(*n)));
+            }
+        }
+        links.into_iter().collect()
+    }
+
+    pub fn link_count(&self) -> usize {
+        self.links().len()
+    }
+
+    pub fn is_linked(&self, a: u32, b: u32) -> bool {
+        self.planet(a).is_some_and(|p| p.neighbours.contains(&b))
+    }
+
+    /// Checks the galaxy for problems the orchestrator would choke on
+    ///
+    /// Returns a human readable message for each problem, empty if the galaxy is valid.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if self.planets.is_empty() {
+            issues.push("The galaxy has no planets".to_string());
+            return issues;
+        }
+
+        for planet in &self.planets {
+            if planet.neighbours.is_empty() {
+                issues.push(format!("Planet {} is isolated", planet.id));
+            }
+            for n in &planet.neighbours {
+                if *n == planet.id {
+                    issues.push(format!("Planet {} is linked to itself", planet.id));
+                } else if self.planet(*n).is_none() {
+                    issues.push(format!(
+                        "Planet {} links to unknown planet {}",
+                        planet.id, n
+                    ));
+                } else if !self.is_linked(*n, planet.id) {
+                    issues.push(format!("Link {} -> {} is not symmetric", planet.id, n));
+                }
+            }
+        }
+
+        // Breadth-first visit from the first planet to find unreachable ones
+        let mut visited = BTreeSet::from([self.planets[0].id]);
+        let mut queue = VecDeque::from([self.planets[0].id]);
+        while let Some(id) = queue.pop_front() {
+            if let Some(planet) = self.planet(id) {
+                for n in &planet.neighbours {
+                    if self.planet(*n).is_some() && visited.insert(*n) {
+                        queue.push_back(*n);
+                    }
+                }
+            }
+        }
+        let unreachable: Vec<String> = self
+            .planets
+            .iter()
+            .filter(|p| !visited.contains(&p.id))
+            .map(|p| p.id.to_string())
+            .collect();
+        if !unreachable.is_empty() {
+            issues.push(format!(
+                "Planets not reachable from planet {}: {}",
+                self.planets[0].id,
+                unreachable.join(", ")
+            ));
+        }
+
+        for explorer in &self.explorers {
+            if self.planet(explorer.planet_id).is_none() {
+                issues.push(format!(
+                    "Explorer {} starts on unknown planet {}",
+                    explorer.id, explorer.planet_id
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+/// Loads a galaxy file in a fresh orchestrator, with the parser the game itself uses
+pub fn load_in_orchestrator(path: &str) -> Result<(), String> {
+    let mut orchestrator = Orchestrator::new()?;
+    orchestrator
+        .initialize_galaxy_by_file(path)
+        .map_err(|e| e.to_string())
+}
+
+// Editing operations
+impl GalaxyFile {
+    /// Adds a planet with the first free ID and returns it
+    pub fn add_planet(&mut self, planet_type: PlanetType) -> u32 {
+        let id = (0..)
+            .find(|id| self.planet(*id).is_none())
+            .unwrap_or_default();
+        self.planets.push(PlanetEntry {
+            id,
+            planet_type,
+            neighbours: BTreeSet::new(),
+        });
+        id
+    }
+
+    /// Removes a planet together with every link and explorer pointing to it
+    pub fn remove_planet(&mut self, id: u32) {
+        self.planets.retain(|p| p.id != id);
+        for planet in &mut self.planets {
+            planet.neighbours.remove(&id);
+        }
+        self.explorers.retain(|e| e.planet_id != id);
+    }
+
+    pub fn cycle_planet_type(&mut self, id: u32) {
+        if let Some(planet) = self.planets.iter_mut().find(|p| p.id == id) {
+            planet.planet_type = planet.planet_type.next();
+        }
+    }
+
+    /// Adds or removes the link between two planets on both sides
+    pub fn toggle_link(&mut self, a: u32, b: u32) {
+        if a == b {
+            return;
+        }
+        let linked = self.is_linked(a, b) || self.is_linked(b, a);
+        for planet in &mut self.planets {
+            let other = if planet.id == a {
+                b
+            } else if planet.id == b {
+                a
+            } else {
+                continue;
+            };
+            if linked {
+                planet.neighbours.remove(&other);
+            } else {
+                planet.neighbours.insert(other);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_planets_explorers_and_comments() {
+        let (galaxy, errors) = GalaxyFile::parse(
+            "# Two planets\nplanet 0 A 1\nplanet 1 c 0 # lowercase type\nexplorer 0 1\n",
+        );
+        assert!(errors.is_empty());
+        assert_eq!(galaxy.planets.len(), 2);
+        assert_eq!(galaxy.planet(1).unwrap().planet_type, PlanetType::C);
+        assert_eq!(galaxy.explorers[0].planet_id, 1);
+        assert!(galaxy.validate().is_empty());
+    }
+
+    #[test]
+    fn rejects_an_unknown_planet_type() {
+        let (galaxy, errors) = GalaxyFile::parse("planet 0 A 1\nplanet 1 E 0\n");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 2);
+        assert!(errors[0].message.contains("unknown planet type `E`"));
+        assert!(galaxy.planet(1).is_none());
+    }
+
+    #[test]
+    fn reports_every_error_with_its_line() {
+        let (_, errors) = GalaxyFile::parse("planet x A\nplanet 0 A 1,y\nmoon 3\nexplorer 0\n");
+        let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn flags_a_link_to_an_unknown_planet() {
+        let (galaxy, errors) = GalaxyFile::parse("planet 0 A 1,7\nplanet 1 B 0\n");
+        assert!(errors.is_empty());
+        assert_eq!(galaxy.validate(), ["Planet 0 links to unknown planet 7"]);
+    }
+
+    #[test]
+    fn flags_an_isolated_planet() {
+        let (galaxy, _) = GalaxyFile::parse("planet 0 A 1\nplanet 1 B 0\nplanet 2 D\n");
+        let issues = galaxy.validate();
+        assert!(issues.contains(&"Planet 2 is isolated".to_string()));
+        assert!(issues.contains(&"Planets not reachable from planet 0: 2".to_string()));
+    }
+
+    #[test]
+    fn flags_one_sided_links_and_misplaced_explorers() {
+        let (galaxy, _) =
+            GalaxyFile::parse("planet 0 A 1\nplanet 1 B 0,2\nplanet 2 C 1,0\nexplorer 0 5\n");
+        assert_eq!(
+            galaxy.validate(),
+            [
+                "Link 2 -> 0 is not symmetric",
+                "Explorer 0 starts on unknown planet 5"
+            ]
+        );
+    }
+
+    #[test]
+    fn saved_files_parse_back_the_same() {
+        let mut galaxy = GalaxyFile::default();
+        let a = galaxy.add_planet(PlanetType::A);
+        let b = galaxy.add_planet(PlanetType::D);
+        galaxy.toggle_link(a, b);
+        galaxy.explorers.push(ExplorerEntry {
+            id: 0,
+            planet_id: b,
+        });
+
+        let (parsed, errors) = GalaxyFile::parse(&galaxy.to_file_string());
+        assert!(errors.is_empty());
+        assert_eq!(parsed.links(), [(a, b)]);
+        assert_eq!(parsed.planet(b).unwrap().planet_type, PlanetType::D);
+        assert_eq!(parsed.explorers[0].planet_id, b);
+    }
+}
@@ -1,12 +1,19 @@
-use crate::app::App;
-use crossterm::event::{self, Event, KeyCode};
-use std::time::{Duration};
+use crate::app::{App, SUNRAY_COARSE_STEP, SUNRAY_FINE_STEP};
+use crate::generator::GeneratorForm;
+use crate::keymap::Action;
+use crate::palette::CommandPalette;
+use crate::ui::{Tab, galaxy_map, row_at};
+use crossterm::event::{self, Event, KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+use std::time::{Duration, Instant};
 
 /// Represents the different states the game can be in
 #[derive(Clone, PartialEq, Debug)]
 pub enum GameState {
     /// Waiting for the player to start the game
     WaitingStart,
+    /// Editing the galaxy file before starting
+    Editing,
     /// Game is actively running
     Running,
     /// Game is paused
@@ -17,51 +24,417 @@ pub enum GameState {
 
 /// Handles user input and updates the game state accordingly
 ///
-/// Polls for keyboard events and processes them based on the current game state.
+/// Polls for keyboard and mouse events and processes them based on the current game state.
 /// Only processes Press events to avoid key repeat issues.
 pub fn handle_game_state(app: &mut App) -> Result<(), String> {
         // Very short timeout for responsive input
         if event::poll(Duration::from_millis(10)).map_err(|_| "Polling error")? {
-            if let Event::Key(key) = event::read().map_err(|_| "Reading events error")? {
-                match (key.code, app.get_game_state()) {
-                    // Global events - respond immediately on key press
-                    (KeyCode::Char('q'), _) => {
-                        app.exit = true;
+            match event::read().map_err(|_| "Reading events error")? {
+                Event::Key(key) => {
+                    // The editor has its own bindings, global ones don't apply there
+                    if app.get_game_state() == GameState::Editing {
+                        handle_editor_input(app, key.code);
+                        return Ok(());
                     }
-                    (KeyCode::Enter, GameState::WaitingStart) => {
-                        app.set_game_state(GameState::Running);
-                        app.orchestrator.start_all()?;
+                    // The help popup is closed by any key
+                    if app.show_help {
+                        app.show_help = false;
+                        return Ok(());
                     }
-                    (KeyCode::Char('p'), GameState::Running) => {
-                        app.set_game_state(GameState::Paused);
+                    // As are the selection description and the notification history
+                    if app.description.is_some() {
+                        app.description = None;
+                        return Ok(());
                     }
-                    (KeyCode::Char('p'), GameState::Paused) => {
-                        app.set_game_state(GameState::Running);
+                    if app.show_notifications {
+                        app.show_notifications = false;
+                        return Ok(());
                     }
-                    (KeyCode::Up, _) => app.set_sunray_increment(),
-                    (KeyCode::Down, _) => app.set_sunray_decrement(),
-
-                    // Navigation events
-                    (KeyCode::Char('w'), _) => {
-                        app.decrement_id_selector();
+                    // The command palette takes every key while open
+                    if app.palette.is_some() {
+                        handle_palette_input(app, key.code)?;
+                        return Ok(());
+                    }
+                    // Same for the generator popup of the start screen
+                    if app.generator_form.is_some() {
+                        handle_generator_input(app, key.code);
+                        return Ok(());
+                    }
+                    // And for the console, where keys are typed in
+                    if app.show_console {
+                        handle_console_input(app, key.code);
+                        return Ok(());
                     }
-                    (KeyCode::Char('s'), _) => {
-                        app.increment_id_selector();
+                    // And for the sunray entry popup
+                    if app.sunray_entry.is_some() {
+                        handle_sunray_entry_input(app, key.code);
+                        return Ok(());
                     }
 
-                    // Toggle log overlay with 'L'
-                    (KeyCode::Char('l'), _) => {
-                        app.show_log_overlay = !app.show_log_overlay;
+                    // Everything else goes through the keymap
+                    if let Some(action) = app.keymap.action_for(&key, &app.get_game_state()) {
+                        apply_action(app, action)?;
                     }
+                }
+                Event::Mouse(mouse) => handle_mouse_input(app, mouse)?,
+                _ => {}
+            }
+    }
+    Ok(())
+}
+
+/// Performs an action, assuming it is available in the current state
+pub(crate) fn apply_action(app: &mut App, action: Action) -> Result<(), String> {
+    match action {
+        Action::Quit => {
+            app.exit = true;
+        }
+        Action::Start => {
+            let Some(file_path) = app.selected_galaxy_file() else {
+                app.start_error = Some(format!("No galaxy file found in {}", app.galaxy_dir));
+                return Ok(());
+            };
+            // Stay on the start screen and show why the galaxy can't be loaded
+            if let Err(e) = app.initialize_by_file(&file_path) {
+                app.start_error = Some(e);
+                return Ok(());
+            }
+            app.start_error = None;
+            if let Some(scenario) = app.scenario.as_mut() {
+                scenario.rewind();
+                log::info!("Running scenario {}", scenario.name);
+            }
+            app.stats.start(&app.planets_info);
+            app.set_game_state(GameState::Running);
+            app.orchestrator.start_all()?;
+        }
+        Action::EditGalaxy => {
+            if let Err(e) = app.open_editor() {
+                app.start_error = Some(e);
+            }
+        }
+        Action::GenerateGalaxy => {
+            app.generator_form = Some(GeneratorForm::new());
+        }
+        // Galaxy file picker
+        Action::PreviousGalaxyFile => app.select_previous_galaxy_file(),
+        Action::NextGalaxyFile => app.select_next_galaxy_file(),
+        Action::NextScenario => app.select_next_scenario(),
+
+        // Resuming goes through a countdown, pausing again or ESC cancels it
+        Action::TogglePause => match app.get_game_state() {
+            GameState::Running => app.set_game_state(GameState::Paused),
+            _ => {
+                app.resume_countdown = match app.resume_countdown {
+                    Some(_) => None,
+                    None => Some(Instant::now()),
+                };
+            }
+        },
+        Action::ResumeNow => {
+            if app.resume_countdown.is_some() {
+                app.resume();
+            }
+        }
+        Action::CancelResume => {
+            app.resume_countdown = None;
+        }
+        Action::SunrayUp => app.change_sunray_probability(SUNRAY_FINE_STEP),
+        Action::SunrayDown => app.change_sunray_probability(-SUNRAY_FINE_STEP),
+        Action::SunrayUpCoarse => app.change_sunray_probability(SUNRAY_COARSE_STEP),
+        Action::SunrayDownCoarse => app.change_sunray_probability(-SUNRAY_COARSE_STEP),
+        Action::EnterSunray => app.sunray_entry = Some(String::new()),
+
+        // Navigation events
+        Action::SelectPreviousPlanet => app.decrement_id_selector(),
+        Action::SelectNextPlanet => app.increment_id_selector(),
+        Action::CyclePlanetSort => app.cycle_planet_sort(),
+        Action::ReversePlanetSort => app.reverse_planet_sort(),
+        Action::CyclePlanetFilter => app.cycle_planet_filter(),
+        Action::SelectPreviousExplorer => app.select_previous_explorer(),
+        Action::SelectNextExplorer => app.select_next_explorer(),
+        Action::ClearSelection => {
+            app.selected_planet = None;
+            app.explorer_table_state.select(None);
+        }
+
+        Action::ToggleLog => {
+            app.show_log_overlay = !app.show_log_overlay;
+        }
+        Action::ScrollLogUp => app.scroll_log(-1),
+        Action::ScrollLogDown => app.scroll_log(1),
+        Action::FilterLog => app.cycle_log_filter(),
+        Action::OpenConsole => app.open_console(),
+        Action::ToggleHelp => {
+            app.show_help = !app.show_help;
+        }
+        Action::ToggleInstructions => {
+            app.instructions_collapsed = !app.instructions_collapsed;
+        }
+
+        // Restart game when ended
+        Action::Restart => app.restart()?,
+        Action::ExportReport => app.export_report(),
+
+        Action::OpenPalette => {
+            // Scoped to the tab being looked at, tabs only exist in the game screen
+            let in_game = matches!(
+                app.get_game_state(),
+                GameState::Running | GameState::Paused
+            );
+            app.palette = Some(CommandPalette::new(in_game.then_some(app.tab)));
+        }
+        Action::CycleTheme => app.cycle_theme(),
+        Action::ToggleAccessibility => app.toggle_accessibility(),
+        Action::DescribeSelection => app.describe_selection(),
+        Action::ToggleNotifications => app.show_notifications = !app.show_notifications,
+
+        Action::ShowOverview => app.tab = Tab::Overview,
+        Action::ShowGalaxyMap => app.tab = Tab::GalaxyMap,
+        Action::ShowPlanets => app.tab = Tab::Planets,
+        Action::ShowExplorers => app.tab = Tab::Explorers,
+        Action::ShowStatistics => app.tab = Tab::Statistics,
+        Action::ShowLogs => app.tab = Tab::Logs,
+        Action::NextTab => app.tab = app.tab.next(),
+    }
+    Ok(())
+}
+
+/// Handles the keys of the galaxy file editor
+///
+/// These keys are fixed, they aren't actions of the keymap: the editor is a
+/// screen of its own and its keys don't clash with the game ones.
+fn handle_editor_input(app: &mut App, code: KeyCode) {
+    if code == KeyCode::Esc {
+        app.close_editor();
+        return;
+    }
+
+    let Some(editor) = app.editor.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Up => editor.move_cursor(-1, 0),
+        KeyCode::Down => editor.move_cursor(1, 0),
+        KeyCode::Left => editor.move_cursor(0, -1),
+        KeyCode::Right => editor.move_cursor(0, 1),
+        KeyCode::Char(' ') | KeyCode::Enter => editor.toggle_link_at_cursor(),
+        KeyCode::Char('a') => editor.add_planet(),
+        KeyCode::Char('d') | KeyCode::Delete => editor.remove_selected_planet(),
+        KeyCode::Char('t') => editor.cycle_selected_type(),
+        KeyCode::Char('s') => editor.save(),
+        KeyCode::Char('r') => editor.reload(),
+        _ => {}
+    }
+}
+
+/// Handles the keys of the galaxy generator popup
+fn handle_generator_input(app: &mut App, code: KeyCode) {
+    if code == KeyCode::Enter {
+        app.generate_galaxy();
+        return;
+    }
+
+    let Some(form) = app.generator_form.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Esc => app.generator_form = None,
+        KeyCode::Up => form.previous_field(),
+        KeyCode::Down => form.next_field(),
+        KeyCode::Left => form.adjust(-1),
+        KeyCode::Right => form.adjust(1),
+        KeyCode::PageDown => form.adjust(-10),
+        KeyCode::PageUp => form.adjust(10),
+        _ => {}
+    }
+}
 
-                    // Restart game when ended
-                    (KeyCode::Char('r'), GameState::Ended) => {
-                        app.set_game_state(GameState::WaitingStart);
-                        // TODO: Add orchestrator reset function
+/// Handles the keys of the command palette, ENTER runs the selected action
+fn handle_palette_input(app: &mut App, code: KeyCode) -> Result<(), String> {
+    let state = app.get_game_state();
+    let Some(palette) = app.palette.as_mut() else {
+        return Ok(());
+    };
+    match code {
+        KeyCode::Enter => {
+            let action = palette.selected_action(&state);
+            app.palette = None;
+            if let Some(action) = action {
+                apply_action(app, action)?;
+            }
+        }
+        KeyCode::Esc => app.palette = None,
+        KeyCode::Up => palette.move_selection(-1, &state),
+        KeyCode::Down => palette.move_selection(1, &state),
+        KeyCode::Backspace => palette.pop(),
+        KeyCode::Tab => palette.toggle_all_panels(),
+        KeyCode::Char(c) => palette.push(c),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles the keys of the scripting console
+fn handle_console_input(app: &mut App, code: KeyCode) {
+    if code == KeyCode::Enter {
+        app.submit_console();
+        return;
+    }
+
+    let Some(console) = app.console.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Esc => app.show_console = false,
+        KeyCode::Up => console.recall(-1),
+        KeyCode::Down => console.recall(1),
+        KeyCode::Backspace => {
+            console.input.pop();
+        }
+        KeyCode::Char(c) => console.input.push(c),
+        _ => {}
+    }
+}
+
+/// Handles the keys of the sunray entry popup
+///
+/// Digits are typed into the entry, arrows and PgUp/PgDn change the
+/// probability right away.
+fn handle_sunray_entry_input(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.submit_sunray_entry(),
+        KeyCode::Esc => app.sunray_entry = None,
+        KeyCode::Up => app.change_sunray_probability(SUNRAY_FINE_STEP),
+        KeyCode::Down => app.change_sunray_probability(-SUNRAY_FINE_STEP),
+        KeyCode::PageUp => app.change_sunray_probability(SUNRAY_COARSE_STEP),
+        KeyCode::PageDown => app.change_sunray_probability(-SUNRAY_COARSE_STEP),
+        KeyCode::Backspace => {
+            if let Some(entry) = app.sunray_entry.as_mut() {
+                entry.pop();
+            }
+        }
+        KeyCode::Char(c @ '0'..='9') => {
+            if let Some(entry) = app.sunray_entry.as_mut()
+                && entry.len() < 3
+            {
+                entry.push(c);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Handles clicks and the scroll wheel, using the areas recorded by the last frame
+///
+/// Left click selects the row, planet or file under the pointer, or opens the
+/// header control; the wheel moves through the list under the pointer.
+fn handle_mouse_input(app: &mut App, mouse: MouseEvent) -> Result<(), String> {
+    let areas = app.click_areas;
+    let pointer = Position::new(mouse.column, mouse.row);
+    let click = matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left));
+    let scroll = match mouse.kind {
+        MouseEventKind::ScrollUp => -1,
+        MouseEventKind::ScrollDown => 1,
+        _ => 0,
+    };
+    if (!click && scroll == 0) || app.palette.is_some() {
+        return Ok(());
+    }
+    if app.show_help {
+        app.show_help = !click;
+        return Ok(());
+    }
+    if app.description.is_some() {
+        if click {
+            app.description = None;
+        }
+        return Ok(());
+    }
+    if app.show_notifications {
+        app.show_notifications = !click;
+        return Ok(());
+    }
+
+    match app.get_game_state() {
+        GameState::WaitingStart if app.generator_form.is_none() => {
+            if !areas.galaxy_files.contains(pointer) {
+                return Ok(());
+            }
+            if click {
+                let area = areas.galaxy_files;
+                let row = row_at(area, area.y + 1, app.galaxy_file_state.offset(), mouse.row);
+                if let Some(i) = row {
+                    app.select_galaxy_file(i);
+                }
+            } else if scroll < 0 {
+                app.select_previous_galaxy_file();
+            } else {
+                app.select_next_galaxy_file();
+            }
+        }
+        GameState::Running | GameState::Paused => {
+            // Popups first, they are drawn over everything else
+            if app.sunray_entry.is_some() {
+                if areas.sunray_control.contains(pointer) {
+                    app.change_sunray_probability(-scroll * SUNRAY_FINE_STEP);
+                } else if click {
+                    app.sunray_entry = None;
+                }
+                return Ok(());
+            }
+            if areas.pause_popup.contains(pointer) {
+                return Ok(());
+            }
+
+            if let Some(i) = areas.tabs.iter().position(|area| area.contains(pointer)) {
+                if click {
+                    app.tab = Tab::ALL[i];
+                }
+            } else if areas.game_state.contains(pointer) && click {
+                apply_action(app, Action::TogglePause)?;
+            } else if areas.sunray.contains(pointer) {
+                match scroll {
+                    0 => app.sunray_entry = Some(String::new()),
+                    _ => app.change_sunray_probability(-scroll * SUNRAY_FINE_STEP),
+                }
+            } else if areas.log_overlay.contains(pointer) {
+                app.scroll_log(scroll);
+            } else if areas.planets_table.contains(pointer) {
+                if click {
+                    let area = areas.planets_table;
+                    let row = row_at(area, area.y + 2, app.table_state.offset(), mouse.row);
+                    if let Some(id) = row.and_then(|i| app.planet_rows().get(i).copied()) {
+                        app.select_planet(id);
+                    }
+                } else if scroll < 0 {
+                    app.decrement_id_selector();
+                } else {
+                    app.increment_id_selector();
+                }
+            } else if areas.explorers_table.contains(pointer) {
+                if click {
+                    let area = areas.explorers_table;
+                    let offset = app.explorer_table_state.offset();
+                    let row = row_at(area, area.y + 2, offset, mouse.row);
+                    if let Some(i) = row.filter(|i| *i < app.explorers_info.len()) {
+                        app.explorer_table_state.select(Some(i));
                     }
-                    _ => {}
+                } else if scroll < 0 {
+                    app.select_previous_explorer();
+                } else {
+                    app.select_next_explorer();
+                }
+            } else if areas.galaxy_map.contains(pointer) && click {
+                let ids: Vec<u32> = app.planets_info.iter().map(|(id, _)| *id).collect();
+                if let Some(id) =
+                    galaxy_map::node_at(areas.galaxy_map, &ids, mouse.column, mouse.row)
+                {
+                    app.select_planet(id);
                 }
             }
+        }
+        _ => {}
     }
     Ok(())
 }
@@ -0,0 +1,519 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::galaxy_file::{self, ExplorerEntry, GalaxyFile, PlanetEntry, PlanetType};
+
+/// Shape of the links between generated planets
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Topology {
+    /// Every planet linked to the previous and the next one
+    Ring,
+    /// Planets on a square grid, linked to the adjacent ones
+    Grid,
+    /// Random connected graph with the given average degree
+    Random { degree: usize },
+    /// Densely linked groups, chained together by a single link
+    Clusters { count: usize },
+    /// Planet 0 linked to every other planet
+    Star,
+}
+
+impl Topology {
+    /// Returns the following shape, keeping the parameter of the current one
+    pub fn next(self) -> Self {
+        let param = self.param();
+        match self {
+            Topology::Ring => Topology::Grid,
+            Topology::Grid => Topology::Random { degree: param },
+            Topology::Random { .. } => Topology::Clusters { count: param },
+            Topology::Clusters { .. } => Topology::Star,
+            Topology::Star => Topology::Ring,
+        }
+    }
+
+    /// Degree for `Random`, number of clusters for `Clusters`, unused otherwise
+    pub fn param(self) -> usize {
+        match self {
+            Topology::Random { degree } => degree,
+            Topology::Clusters { count } => count,
+            _ => 3,
+        }
+    }
+
+    pub fn with_param(self, param: usize) -> Self {
+        match self {
+            Topology::Random { .. } => Topology::Random { degree: param },
+            Topology::Clusters { .. } => Topology::Clusters { count: param },
+            other => other,
+        }
+    }
+
+    /// Parses `ring`, `grid`, `star`, `random:<degree>` or `clusters:<count>`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (name, param) = match text.split_once(':') {
+            Some((name, param)) => (
+                name,
+                Some(
+                    param
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid topology parameter `{}`", param))?,
+                ),
+            ),
+            None => (text, None),
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "ring" => Ok(Topology::Ring),
+            "grid" => Ok(Topology::Grid),
+            "star" => Ok(Topology::Star),
+            "random" => Ok(Topology::Random {
+                degree: param.unwrap_or(3),
+            }),
+            "clusters" => Ok(Topology::Clusters {
+                count: param.unwrap_or(3),
+            }),
+            _ => Err(format!("Unknown topology `{}`", text)),
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topology::Ring => write!(f, "ring"),
+            Topology::Grid => write!(f, "grid"),
+            Topology::Random { degree } => write!(f, "random:{}", degree),
+            Topology::Clusters { count } => write!(f, "clusters:{}", count),
+            Topology::Star => write!(f, "star"),
+        }
+    }
+}
+
+/// Parameters of a generated galaxy
+#[derive(Clone, Debug)]
+pub struct GeneratorConfig {
+    pub planets: usize,
+    /// Relative weight of each planet type, in A, B, C, D order
+    pub type_mix: [u32; 4],
+    pub explorers: usize,
+    pub topology: Topology,
+    pub seed: u64,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            planets: 10,
+            type_mix: [1, 1, 1, 1],
+            explorers: 2,
+            topology: Topology::Ring,
+            seed: 42,
+        }
+    }
+}
+
+impl GeneratorConfig {
+    /// Default name for the generated file, so different parameters don't overwrite each other
+    pub fn file_name(&self) -> String {
+        format!(
+            "galaxy_{}_{}p_{}.txt",
+            self.topology.to_string().replace(':', "-"),
+            self.planets,
+            self.seed
+        )
+    }
+
+    /// Parses the options of the `generate` command line
+    ///
+    /// Returns the config and the output path (defaults to `file_name()`).
+    pub fn from_args(args: &[String]) -> Result<(Self, String), String> {
+        let mut config = Self::default();
+        let mut output = None;
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", flag))?;
+            let number = || {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| format!("Invalid number `{}` for {}", value, flag))
+            };
+            match flag.as_str() {
+                "--planets" => config.planets = number()? as usize,
+                "--explorers" => config.explorers = number()? as usize,
+                "--seed" => config.seed = number()?,
+                "--topology" => config.topology = Topology::parse(value)?,
+                "--mix" => {
+                    let weights: Vec<u32> = value
+                        .split(',')
+                        .map(|w| w.trim().parse::<u32>())
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| format!("Invalid type mix `{}`", value))?;
+                    config.type_mix = weights
+                        .try_into()
+                        .map_err(|_| "The type mix needs 4 weights: A,B,C,D".to_string())?;
+                }
+                "--out" => output = Some(value.clone()),
+                _ => return Err(format!("Unknown option {}", flag)),
+            }
+        }
+
+        let output = output.unwrap_or_else(|| config.file_name());
+        Ok((config, output))
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.planets == 0 {
+            return Err("At least one planet is needed".to_string());
+        }
+        if self.type_mix.iter().all(|w| *w == 0) {
+            return Err("At least one planet type must have a weight".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Generates a galaxy from the given parameters
+///
+/// The same config (seed included) always produces the same galaxy.
+pub fn generate(config: &GeneratorConfig) -> Result<GalaxyFile, String> {
+    config.check()?;
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let n = config.planets;
+
+    let mut planets: Vec<PlanetEntry> = (0..n)
+        .map(|id| PlanetEntry {
+            id: id as u32,
+            planet_type: random_type(&mut rng, &config.type_mix),
+            neighbours: BTreeSet::new(),
+        })
+        .collect();
+
+    let mut link = |a: usize, b: usize| {
+        if a != b {
+            planets[a].neighbours.insert(b as u32);
+            planets[b].neighbours.insert(a as u32);
+        }
+    };
+
+    match config.topology {
+        Topology::Ring => {
+            for i in 0..n {
+                link(i, (i + 1) % n);
+            }
+        }
+        Topology::Grid => {
+            let cols = (n as f64).sqrt().ceil() as usize;
+            for i in 0..n {
+                if (i + 1) % cols != 0 && i + 1 < n {
+                    link(i, i + 1);
+                }
+                if i + cols < n {
+                    link(i, i + cols);
+                }
+            }
+        }
+        Topology::Star => {
+            for i in 1..n {
+                link(0, i);
+            }
+        }
+        Topology::Random { degree } => {
+            for (a, b) in random_connected_links(&mut rng, n, degree) {
+                link(a, b);
+            }
+        }
+        Topology::Clusters { count } => {
+            let count = count.clamp(1, n);
+            let size = n.div_ceil(count);
+            let mut previous: Option<(usize, usize)> = None;
+            for start in (0..n).step_by(size) {
+                let end = (start + size).min(n);
+                for (a, b) in random_connected_links(&mut rng, end - start, 3) {
+                    link(start + a, start + b);
+                }
+                // Bridge between a random planet of the previous cluster and one of this
+                if let Some((prev_start, prev_end)) = previous {
+                    link(
+                        rng.random_range(prev_start..prev_end),
+                        rng.random_range(start..end),
+                    );
+                }
+                previous = Some((start, end));
+            }
+        }
+    }
+
+    let explorers = (0..config.explorers)
+        .map(|id| ExplorerEntry {
+            id: id as u32,
+            planet_id: rng.random_range(0..n) as u32,
+        })
+        .collect();
+
+    Ok(GalaxyFile { planets, explorers })
+}
+
+/// Generates a galaxy, saves it to `path` and checks the orchestrator loads it
+///
+/// A rejected file stays on disk, so it can be fixed in the editor.
+pub fn generate_file(config: &GeneratorConfig, path: &str) -> Result<(), String> {
+    generate(config)?.save(path)?;
+    galaxy_file::load_in_orchestrator(path)
+        .map_err(|e| format!("The orchestrator rejects {}: {}", path, e))
+}
+
+fn random_type(rng: &mut StdRng, type_mix: &[u32; 4]) -> PlanetType {
+    let types = [PlanetType::A, PlanetType::B, PlanetType::C, PlanetType::D];
+    let total: u32 = type_mix.iter().sum();
+    let mut pick = rng.random_range(0..total);
+    for (planet_type, weight) in types.iter().zip(type_mix) {
+        if pick < *weight {
+            return *planet_type;
+        }
+        pick -= weight;
+    }
+    PlanetType::A
+}
+
+/// Links of a random connected graph of `n` nodes with about `degree` links per node
+///
+/// A random spanning tree guarantees connectivity, extra links are then added at random.
+fn random_connected_links(rng: &mut StdRng, n: usize, degree: usize) -> BTreeSet<(usize, usize)> {
+    let mut links = BTreeSet::new();
+    for i in 1..n {
+        let j = rng.random_range(0..i);
+        links.insert((j, i));
+    }
+
+    let max_links = n * n.saturating_sub(1) / 2;
+    let target = (n * degree / 2).min(max_links);
+    while links.len() < target {
+        let a = rng.random_range(0..n);
+        let b = rng.random_range(0..n);
+        if a != b {
+            links.insert((a.min(b), a.max(b)));
+        }
+    }
+    links
+}
+
+/// Editable field of the generator form
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GeneratorField {
+    Planets,
+    Explorers,
+    Topology,
+    ShapeParam,
+    /// Weight of the planet type at this index of `type_mix`
+    Mix(usize),
+    Seed,
+}
+
+const FIELDS: [GeneratorField; 9] = [
+    GeneratorField::Planets,
+    GeneratorField::Explorers,
+    GeneratorField::Topology,
+    GeneratorField::ShapeParam,
+    GeneratorField::Mix(0),
+    GeneratorField::Mix(1),
+    GeneratorField::Mix(2),
+    GeneratorField::Mix(3),
+    GeneratorField::Seed,
+];
+
+/// State of the generator popup of the start screen
+pub struct GeneratorForm {
+    pub config: GeneratorConfig,
+    /// Index into the form fields
+    pub field: usize,
+    /// Error of the last generation attempt
+    pub error: Option<String>,
+}
+
+impl GeneratorForm {
+    pub fn new() -> Self {
+        Self {
+            config: GeneratorConfig::default(),
+            field: 0,
+            error: None,
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        self.field = (self.field + 1) % FIELDS.len();
+    }
+
+    pub fn previous_field(&mut self) {
+        self.field = (self.field + FIELDS.len() - 1) % FIELDS.len();
+    }
+
+    /// Changes the value of the selected field by `delta` steps
+    pub fn adjust(&mut self, delta: i64) {
+        let config = &mut self.config;
+        let step = |value: usize, min: usize| (value as i64 + delta).max(min as i64) as usize;
+        match FIELDS[self.field] {
+            GeneratorField::Planets => config.planets = step(config.planets, 1),
+            GeneratorField::Explorers => config.explorers = step(config.explorers, 0),
+            GeneratorField::Topology => config.topology = config.topology.next(),
+            GeneratorField::ShapeParam => {
+                config.topology = config.topology.with_param(step(config.topology.param(), 1))
+            }
+            GeneratorField::Mix(i) => {
+                config.type_mix[i] = step(config.type_mix[i] as usize, 0) as u32
+            }
+            GeneratorField::Seed => config.seed = config.seed.wrapping_add_signed(delta),
+        }
+    }
+
+    /// Label and current value of every field, in display order
+    pub fn rows(&self) -> Vec<(String, String)> {
+        let config = &self.config;
+        FIELDS
+            .iter()
+            .map(|field| match field {
+                GeneratorField::Planets => ("Planets".to_string(), config.planets.to_string()),
+                GeneratorField::Explorers => {
+                    ("Explorers".to_string(), config.explorers.to_string())
+                }
+                GeneratorField::Topology => ("Topology".to_string(), config.topology.to_string()),
+                GeneratorField::ShapeParam => match config.topology {
+                    Topology::Random { degree } => ("Degree".to_string(), degree.to_string()),
+                    Topology::Clusters { count } => ("Clusters".to_string(), count.to_string()),
+                    _ => ("Shape param".to_string(), "-".to_string()),
+                },
+                GeneratorField::Mix(i) => (
+                    format!("Type {} weight", ["A", "B", "C", "D"][*i]),
+                    config.type_mix[*i].to_string(),
+                ),
+                GeneratorField::Seed => ("Seed".to_string(), config.seed.to_string()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHAPES: [Topology; 8] = [
+        Topology::Ring,
+        Topology::Grid,
+        Topology::Star,
+        Topology::Random { degree: 0 },
+        Topology::Random { degree: 3 },
+        Topology::Clusters { count: 1 },
+        Topology::Clusters { count: 3 },
+        Topology::Clusters { count: 100 },
+    ];
+
+    /// Planets reached from planet 0 following the links
+    fn reachable(galaxy: &GalaxyFile) -> usize {
+        let mut visited = BTreeSet::from([0]);
+        let mut stack = vec![0];
+        while let Some(id) = stack.pop() {
+            for n in &galaxy.planet(id).unwrap().neighbours {
+                if visited.insert(*n) {
+                    stack.push(*n);
+                }
+            }
+        }
+        visited.len()
+    }
+
+    #[test]
+    fn every_shape_and_size_is_connected() {
+        for topology in SHAPES {
+            for planets in [1, 2, 3, 5, 10, 17, 50] {
+                for seed in 0..4 {
+                    let config = GeneratorConfig {
+                        planets,
+                        topology,
+                        seed,
+                        ..GeneratorConfig::default()
+                    };
+                    let galaxy = generate(&config).unwrap();
+                    let context = format!("{} with {} planets, seed {}", topology, planets, seed);
+                    assert_eq!(galaxy.planets.len(), planets, "{}", context);
+                    assert_eq!(reachable(&galaxy), planets, "{}", context);
+                    for planet in &galaxy.planets {
+                        assert!(!planet.neighbours.contains(&planet.id), "{}", context);
+                        for n in &planet.neighbours {
+                            assert!(galaxy.is_linked(*n, planet.id), "{}", context);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn a_single_planet_has_no_links() {
+        for topology in SHAPES {
+            let config = GeneratorConfig {
+                planets: 1,
+                topology,
+                ..GeneratorConfig::default()
+            };
+            let galaxy = generate(&config).unwrap();
+            assert!(galaxy.links().is_empty(), "{}", topology);
+            assert!(galaxy.explorers.iter().all(|e| e.planet_id == 0));
+        }
+    }
+
+    #[test]
+    fn generated_files_load_in_the_orchestrator() {
+        let dir = std::env::temp_dir();
+        for topology in SHAPES {
+            for planets in [1, 7] {
+                let config = GeneratorConfig {
+                    planets,
+                    topology,
+                    ..GeneratorConfig::default()
+                };
+                let path = dir.join(config.file_name()).display().to_string();
+                let result = generate_file(&config, &path);
+                std::fs::remove_file(&path).ok();
+                assert_eq!(result, Ok(()), "{} with {} planets", topology, planets);
+            }
+        }
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_galaxy() {
+        let config = GeneratorConfig {
+            topology: Topology::Random { degree: 4 },
+            planets: 20,
+            ..GeneratorConfig::default()
+        };
+        let first = generate(&config).unwrap().to_file_string();
+        assert_eq!(generate(&config).unwrap().to_file_string(), first);
+    }
+
+    #[test]
+    fn rejects_empty_galaxies_and_type_mixes() {
+        let no_planets = GeneratorConfig {
+            planets: 0,
+            ..GeneratorConfig::default()
+        };
+        assert!(generate(&no_planets).is_err());
+        let no_types = GeneratorConfig {
+            type_mix: [0; 4],
+            ..GeneratorConfig::default()
+        };
+        assert!(generate(&no_types).is_err());
+    }
+
+    #[test]
+    fn topologies_parse_back_from_their_name() {
+        for topology in SHAPES {
+            assert_eq!(Topology::parse(&topology.to_string()), Ok(topology));
+        }
+        assert!(Topology::parse("random:x").is_err());
+        assert!(Topology::parse("torus").is_err());
+    }
+}
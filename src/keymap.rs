@@ -0,0 +1,703 @@
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::SUNRAY_COARSE_STEP;
+use crate::game_state::GameState;
+
+/// Everything the player can trigger from the keyboard
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Quit,
+    Start,
+    EditGalaxy,
+    GenerateGalaxy,
+    PreviousGalaxyFile,
+    NextGalaxyFile,
+    NextScenario,
+    TogglePause,
+    ResumeNow,
+    CancelResume,
+    SunrayUp,
+    SunrayDown,
+    SunrayUpCoarse,
+    SunrayDownCoarse,
+    EnterSunray,
+    SelectPreviousPlanet,
+    SelectNextPlanet,
+    CyclePlanetSort,
+    ReversePlanetSort,
+    CyclePlanetFilter,
+    SelectPreviousExplorer,
+    SelectNextExplorer,
+    ClearSelection,
+    ToggleLog,
+    ScrollLogUp,
+    ScrollLogDown,
+    FilterLog,
+    OpenConsole,
+    ToggleHelp,
+    ToggleInstructions,
+    Restart,
+    ExportReport,
+    OpenPalette,
+    CycleTheme,
+    ToggleAccessibility,
+    DescribeSelection,
+    ToggleNotifications,
+    ShowOverview,
+    ShowGalaxyMap,
+    ShowPlanets,
+    ShowExplorers,
+    ShowStatistics,
+    ShowLogs,
+    NextTab,
+}
+
+impl Action {
+    pub const ALL: [Action; 44] = [
+        Action::Quit,
+        Action::Start,
+        Action::EditGalaxy,
+        Action::GenerateGalaxy,
+        Action::PreviousGalaxyFile,
+        Action::NextGalaxyFile,
+        Action::NextScenario,
+        Action::TogglePause,
+        Action::ResumeNow,
+        Action::CancelResume,
+        Action::SunrayUp,
+        Action::SunrayDown,
+        Action::SunrayUpCoarse,
+        Action::SunrayDownCoarse,
+        Action::EnterSunray,
+        Action::SelectPreviousPlanet,
+        Action::SelectNextPlanet,
+        Action::CyclePlanetSort,
+        Action::ReversePlanetSort,
+        Action::CyclePlanetFilter,
+        Action::SelectPreviousExplorer,
+        Action::SelectNextExplorer,
+        Action::ClearSelection,
+        Action::ToggleLog,
+        Action::ScrollLogUp,
+        Action::ScrollLogDown,
+        Action::FilterLog,
+        Action::OpenConsole,
+        Action::ToggleHelp,
+        Action::ToggleInstructions,
+        Action::Restart,
+        Action::ExportReport,
+        Action::OpenPalette,
+        Action::CycleTheme,
+        Action::ToggleAccessibility,
+        Action::DescribeSelection,
+        Action::ToggleNotifications,
+        Action::ShowOverview,
+        Action::ShowGalaxyMap,
+        Action::ShowPlanets,
+        Action::ShowExplorers,
+        Action::ShowStatistics,
+        Action::ShowLogs,
+        Action::NextTab,
+    ];
+
+    /// Name used in the keymap file
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Start => "start",
+            Action::EditGalaxy => "edit_galaxy",
+            Action::GenerateGalaxy => "generate_galaxy",
+            Action::PreviousGalaxyFile => "previous_galaxy_file",
+            Action::NextGalaxyFile => "next_galaxy_file",
+            Action::NextScenario => "next_scenario",
+            Action::TogglePause => "toggle_pause",
+            Action::ResumeNow => "resume_now",
+            Action::CancelResume => "cancel_resume",
+            Action::SunrayUp => "sunray_up",
+            Action::SunrayDown => "sunray_down",
+            Action::SunrayUpCoarse => "sunray_up_coarse",
+            Action::SunrayDownCoarse => "sunray_down_coarse",
+            Action::EnterSunray => "enter_sunray",
+            Action::SelectPreviousPlanet => "select_previous_planet",
+            Action::SelectNextPlanet => "select_next_planet",
+            Action::CyclePlanetSort => "cycle_planet_sort",
+            Action::ReversePlanetSort => "reverse_planet_sort",
+            Action::CyclePlanetFilter => "cycle_planet_filter",
+            Action::SelectPreviousExplorer => "select_previous_explorer",
+            Action::SelectNextExplorer => "select_next_explorer",
+            Action::ClearSelection => "clear_selection",
+            Action::ToggleLog => "toggle_log",
+            Action::ScrollLogUp => "scroll_log_up",
+            Action::ScrollLogDown => "scroll_log_down",
+            Action::FilterLog => "filter_log",
+            Action::OpenConsole => "open_console",
+            Action::ToggleHelp => "toggle_help",
+            Action::ToggleInstructions => "toggle_instructions",
+            Action::Restart => "restart",
+            Action::ExportReport => "export_report",
+            Action::OpenPalette => "open_palette",
+            Action::CycleTheme => "cycle_theme",
+            Action::ToggleAccessibility => "toggle_accessibility",
+            Action::DescribeSelection => "describe_selection",
+            Action::ToggleNotifications => "toggle_notifications",
+            Action::ShowOverview => "show_overview",
+            Action::ShowGalaxyMap => "show_galaxy_map",
+            Action::ShowPlanets => "show_planets",
+            Action::ShowExplorers => "show_explorers",
+            Action::ShowStatistics => "show_statistics",
+            Action::ShowLogs => "show_logs",
+            Action::NextTab => "next_tab",
+        }
+    }
+
+    /// Text shown next to the key in the instructions
+    pub fn description(self) -> String {
+        let text = match self {
+            Action::Quit => "Quit",
+            Action::Start => "Start",
+            Action::EditGalaxy => "Edit Galaxy",
+            Action::GenerateGalaxy => "Generate Galaxy",
+            Action::PreviousGalaxyFile => "Previous Galaxy File",
+            Action::NextGalaxyFile => "Next Galaxy File",
+            Action::NextScenario => "Choose Scenario",
+            Action::TogglePause => "Pause/Resume",
+            Action::ResumeNow => "Resume Now",
+            Action::CancelResume => "Stay Paused",
+            Action::SunrayUp => "Increase Sunray %",
+            Action::SunrayDown => "Decrease Sunray %",
+            Action::SunrayUpCoarse => {
+                return format!("Increase Sunray % by {}", SUNRAY_COARSE_STEP);
+            }
+            Action::SunrayDownCoarse => {
+                return format!("Decrease Sunray % by {}", SUNRAY_COARSE_STEP);
+            }
+            Action::EnterSunray => "Type Sunray %",
+            Action::SelectPreviousPlanet => "Select Previous Planet",
+            Action::SelectNextPlanet => "Select Next Planet",
+            Action::CyclePlanetSort => "Sort Planets By Next Column",
+            Action::ReversePlanetSort => "Reverse Planet Order",
+            Action::CyclePlanetFilter => "Filter Planets",
+            Action::SelectPreviousExplorer => "Select Previous Explorer",
+            Action::SelectNextExplorer => "Select Next Explorer",
+            Action::ClearSelection => "Clear Selection",
+            Action::ToggleLog => "Toggle Log Overlay",
+            Action::ScrollLogUp => "Scroll Log Up",
+            Action::ScrollLogDown => "Scroll Log Down",
+            Action::FilterLog => "Filter Log to Selection",
+            Action::OpenConsole => "Open Script Console",
+            Action::ToggleHelp => "Help",
+            Action::ToggleInstructions => "Collapse/Expand Instructions",
+            Action::Restart => "Restart",
+            Action::ExportReport => "Export Report",
+            Action::OpenPalette => "Command Palette",
+            Action::CycleTheme => "Next Colour Theme",
+            Action::ToggleAccessibility => "Accessibility Mode",
+            Action::DescribeSelection => "Describe Selection",
+            Action::ToggleNotifications => "Notification History",
+            Action::ShowOverview => "Overview Tab",
+            Action::ShowGalaxyMap => "Galaxy Map Tab",
+            Action::ShowPlanets => "Planets Tab",
+            Action::ShowExplorers => "Explorers Tab",
+            Action::ShowStatistics => "Statistics Tab",
+            Action::ShowLogs => "Logs Tab",
+            Action::NextTab => "Next Tab",
+        };
+        text.to_string()
+    }
+
+    /// Whether the action does something in the given state
+    pub fn is_available(self, state: &GameState) -> bool {
+        match self {
+            Action::Quit => true,
+            Action::OpenPalette
+            | Action::ToggleHelp
+            | Action::CycleTheme
+            | Action::ToggleAccessibility => *state != GameState::Editing,
+            Action::Start
+            | Action::EditGalaxy
+            | Action::GenerateGalaxy
+            | Action::PreviousGalaxyFile
+            | Action::NextGalaxyFile
+            | Action::NextScenario => *state == GameState::WaitingStart,
+            Action::TogglePause
+            | Action::SunrayUp
+            | Action::SunrayDown
+            | Action::SunrayUpCoarse
+            | Action::SunrayDownCoarse
+            | Action::EnterSunray
+            | Action::SelectPreviousPlanet
+            | Action::SelectNextPlanet
+            | Action::CyclePlanetSort
+            | Action::ReversePlanetSort
+            | Action::CyclePlanetFilter
+            | Action::SelectPreviousExplorer
+            | Action::SelectNextExplorer
+            | Action::ClearSelection
+            | Action::ToggleLog
+            | Action::ScrollLogUp
+            | Action::ScrollLogDown
+            | Action::FilterLog
+            | Action::OpenConsole
+            | Action::ToggleInstructions
+            | Action::DescribeSelection
+            | Action::ToggleNotifications
+            | Action::ShowOverview
+            | Action::ShowGalaxyMap
+            | Action::ShowPlanets
+            | Action::ShowExplorers
+            | Action::ShowStatistics
+            | Action::ShowLogs
+            | Action::NextTab => matches!(state, GameState::Running | GameState::Paused),
+            Action::ResumeNow | Action::CancelResume => *state == GameState::Paused,
+            Action::Restart | Action::ExportReport => *state == GameState::Ended,
+        }
+    }
+
+    /// Part of the UI the action is about, to group the help popup
+    pub fn group(self) -> ActionGroup {
+        match self {
+            Action::Start
+            | Action::EditGalaxy
+            | Action::GenerateGalaxy
+            | Action::PreviousGalaxyFile
+            | Action::NextGalaxyFile
+            | Action::NextScenario => ActionGroup::StartScreen,
+            Action::SelectPreviousPlanet
+            | Action::SelectNextPlanet
+            | Action::CyclePlanetSort
+            | Action::ReversePlanetSort
+            | Action::CyclePlanetFilter
+            | Action::ClearSelection => ActionGroup::Planets,
+            Action::SelectPreviousExplorer | Action::SelectNextExplorer => ActionGroup::Explorers,
+            Action::ToggleLog
+            | Action::ScrollLogUp
+            | Action::ScrollLogDown
+            | Action::FilterLog
+            | Action::ToggleNotifications => ActionGroup::Log,
+            Action::ResumeNow | Action::CancelResume => ActionGroup::Paused,
+            Action::Restart | Action::ExportReport => ActionGroup::EndScreen,
+            Action::ShowOverview
+            | Action::ShowGalaxyMap
+            | Action::ShowPlanets
+            | Action::ShowExplorers
+            | Action::ShowStatistics
+            | Action::ShowLogs
+            | Action::NextTab => ActionGroup::Tabs,
+            _ => ActionGroup::Global,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// Contexts the actions are grouped by in the help popup
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ActionGroup {
+    Global,
+    Tabs,
+    StartScreen,
+    Planets,
+    Explorers,
+    Log,
+    Paused,
+    EndScreen,
+}
+
+impl ActionGroup {
+    pub const ALL: [ActionGroup; 8] = [
+        ActionGroup::Global,
+        ActionGroup::Tabs,
+        ActionGroup::StartScreen,
+        ActionGroup::Planets,
+        ActionGroup::Explorers,
+        ActionGroup::Log,
+        ActionGroup::Paused,
+        ActionGroup::EndScreen,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            ActionGroup::Global => "Global",
+            ActionGroup::Tabs => "Tabs",
+            ActionGroup::StartScreen => "Start Screen",
+            ActionGroup::Planets => "Planets Table",
+            ActionGroup::Explorers => "Explorers Table",
+            ActionGroup::Log => "Log Overlay",
+            ActionGroup::Paused => "Paused",
+            ActionGroup::EndScreen => "End Screen",
+        }
+    }
+}
+
+/// A key, with the Ctrl/Alt modifiers it must be pressed with
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    const fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn char(c: char) -> Self {
+        Self::new(KeyCode::Char(c))
+    }
+
+    /// Parses keys like `q`, `Up`, `Enter`, `F1`, `Ctrl-p` or `Alt+x`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = text;
+        while let Some((modifier, rest)) = key.split_once(['-', '+']).filter(|(_, r)| !r.is_empty())
+        {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                _ => return Err(format!("Unknown modifier `{}` in `{}`", modifier, text)),
+            }
+            key = rest;
+        }
+
+        let code = match key.to_ascii_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            lower => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) => KeyCode::F(n),
+                        None => return Err(format!("Unknown key `{}`", text)),
+                    },
+                }
+            }
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    /// Whether a key event triggers this binding (Shift is ignored, it's part of the char)
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        self.code == key.code && self.modifiers == key.modifiers & relevant
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        match self.code {
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Enter => write!(f, "ENTER"),
+            KeyCode::Esc => write!(f, "ESC"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Char(' ') => write!(f, "SPACE"),
+            // As bound, `k` and `K` are different keys
+            KeyCode::Char(c) => write!(f, "{}", c),
+            other => write!(f, "{}", other),
+        }
+    }
+}
+
+/// Keys bound to each action
+///
+/// The same key can be bound to several actions as long as they are
+/// available in different game states (e.g. ENTER starts and resumes).
+pub struct KeyMap {
+    bindings: Vec<(Action, Vec<KeyBinding>)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        use KeyBinding as K;
+        let bindings = Action::ALL
+            .into_iter()
+            .map(|action| {
+                let keys = match action {
+                    Action::Quit => vec![K::char('q')],
+                    Action::Start => vec![K::new(KeyCode::Enter)],
+                    Action::EditGalaxy => vec![K::char('e')],
+                    Action::GenerateGalaxy => vec![K::char('g')],
+                    Action::PreviousGalaxyFile => vec![K::new(KeyCode::Up), K::char('w')],
+                    Action::NextGalaxyFile => vec![K::new(KeyCode::Down), K::char('s')],
+                    Action::NextScenario => vec![K::char('c')],
+                    Action::TogglePause => vec![K::char('p')],
+                    Action::ResumeNow => vec![K::new(KeyCode::Enter)],
+                    Action::CancelResume => vec![K::new(KeyCode::Esc)],
+                    Action::SunrayUp => vec![K::new(KeyCode::Up)],
+                    Action::SunrayDown => vec![K::new(KeyCode::Down)],
+                    Action::SunrayUpCoarse => vec![K::new(KeyCode::PageUp)],
+                    Action::SunrayDownCoarse => vec![K::new(KeyCode::PageDown)],
+                    Action::EnterSunray => vec![K::char('%')],
+                    Action::SelectPreviousPlanet => vec![K::char('w')],
+                    Action::SelectNextPlanet => vec![K::char('s')],
+                    Action::CyclePlanetSort => vec![K::char('o')],
+                    Action::ReversePlanetSort => vec![K::char('r')],
+                    Action::CyclePlanetFilter => vec![K::char('f')],
+                    // Only in the command palette and with the mouse by default
+                    Action::SelectPreviousExplorer
+                    | Action::SelectNextExplorer
+                    | Action::ClearSelection => vec![],
+                    Action::ToggleLog => vec![K::char('l')],
+                    Action::ScrollLogUp => vec![K::char('[')],
+                    Action::ScrollLogDown => vec![K::char(']')],
+                    Action::FilterLog => vec![K::char('e')],
+                    Action::OpenConsole => vec![K::char(':')],
+                    Action::ToggleHelp => vec![K::char('?')],
+                    Action::ToggleInstructions => vec![K::char('i')],
+                    Action::Restart => vec![K::char('r')],
+                    Action::ExportReport => vec![K::char('x')],
+                    Action::OpenPalette => vec![K {
+                        code: KeyCode::Char('p'),
+                        modifiers: KeyModifiers::CONTROL,
+                    }],
+                    Action::CycleTheme => vec![K::char('t')],
+                    Action::ToggleAccessibility => vec![K::char('a')],
+                    Action::DescribeSelection => vec![K::char('d')],
+                    Action::ToggleNotifications => vec![K::char('n')],
+                    Action::ShowOverview => vec![K::char('1')],
+                    Action::ShowGalaxyMap => vec![K::char('2')],
+                    Action::ShowPlanets => vec![K::char('3')],
+                    Action::ShowExplorers => vec![K::char('4')],
+                    Action::ShowStatistics => vec![K::char('5')],
+                    Action::ShowLogs => vec![K::char('6')],
+                    Action::NextTab => vec![K::new(KeyCode::Tab)],
+                };
+                (action, keys)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl KeyMap {
+    /// Loads the keymap file named by KEYMAP_FILE (default `keymap.toml`)
+    ///
+    /// The file has a `[bindings]` table mapping action names to lists of keys,
+    /// e.g. `sunray_up = ["Up", "k"]`. Actions not in the file keep their
+    /// default keys; a missing file means all defaults. Problems are logged
+    /// and the faulty entry ignored.
+    pub fn load() -> Self {
+        let path = std::env::var("KEYMAP_FILE").unwrap_or_else(|_| "keymap.toml".to_string());
+        let mut keymap = Self::default();
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return keymap;
+        };
+        let table = match content.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => {
+                log::warn!("Ignoring keymap {}: {}", path, e);
+                return keymap;
+            }
+        };
+        let Some(bindings) = table.get("bindings").and_then(|b| b.as_table()) else {
+            log::warn!("Keymap {} has no [bindings] table", path);
+            return keymap;
+        };
+
+        for (name, keys) in bindings {
+            let Some(action) = Action::from_name(name) else {
+                log::warn!("Keymap {}: unknown action `{}`", path, name);
+                continue;
+            };
+            let keys: Result<Vec<KeyBinding>, String> = match keys.as_array() {
+                Some(keys) => keys
+                    .iter()
+                    .map(|key| {
+                        key.as_str()
+                            .ok_or_else(|| "keys must be strings".to_string())
+                            .and_then(KeyBinding::parse)
+                    })
+                    .collect(),
+                None => Err("expected a list of keys".to_string()),
+            };
+            match keys {
+                Ok(keys) => keymap.set(action, keys),
+                Err(e) => log::warn!("Keymap {}: `{}`: {}", path, name, e),
+            }
+        }
+        log::info!("Loaded keymap {}", path);
+        keymap
+    }
+
+    fn set(&mut self, action: Action, keys: Vec<KeyBinding>) {
+        if let Some((_, bound)) = self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            *bound = keys;
+        }
+    }
+
+    /// Action triggered by a key event in the given state, if any
+    pub fn action_for(&self, key: &KeyEvent, state: &GameState) -> Option<Action> {
+        self.bindings
+            .iter()
+            .filter(|(action, _)| action.is_available(state))
+            .find(|(_, keys)| keys.iter().any(|k| k.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys_for(&self, action: Action) -> &[KeyBinding] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Keys of an action joined for display, e.g. `↑/w`
+    pub fn label(&self, action: Action) -> String {
+        let keys: Vec<String> = self
+            .keys_for(action)
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        if keys.is_empty() {
+            "-".to_string()
+        } else {
+            keys.join("/")
+        }
+    }
+
+    /// Bound actions available in the given state, in declaration order
+    pub fn available(&self, state: &GameState) -> Vec<Action> {
+        self.bindings
+            .iter()
+            .filter(|(action, keys)| action.is_available(state) && !keys.is_empty())
+            .map(|(action, _)| *action)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_named_keys_ignoring_case() {
+        for (text, code) in [
+            ("Up", KeyCode::Up),
+            ("ENTER", KeyCode::Enter),
+            ("esc", KeyCode::Esc),
+            ("Space", KeyCode::Char(' ')),
+            ("PageDown", KeyCode::PageDown),
+            ("f5", KeyCode::F(5)),
+            ("F12", KeyCode::F(12)),
+        ] {
+            assert_eq!(
+                KeyBinding::parse(text),
+                Ok(KeyBinding::new(code)),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_the_case_of_chars() {
+        assert_eq!(KeyBinding::parse("k"), Ok(KeyBinding::char('k')));
+        assert_eq!(KeyBinding::parse("K"), Ok(KeyBinding::char('K')));
+        assert_eq!(KeyBinding::char('K').to_string(), "K");
+        assert_eq!(KeyBinding::char('k').to_string(), "k");
+    }
+
+    #[test]
+    fn parses_modifiers_with_either_separator() {
+        let ctrl_p = KeyBinding {
+            code: KeyCode::Char('p'),
+            modifiers: KeyModifiers::CONTROL,
+        };
+        assert_eq!(KeyBinding::parse("Ctrl-p"), Ok(ctrl_p));
+        assert_eq!(KeyBinding::parse("ctrl+p"), Ok(ctrl_p));
+        assert_eq!(
+            KeyBinding::parse("Ctrl-Alt-Up"),
+            Ok(KeyBinding {
+                code: KeyCode::Up,
+                modifiers: KeyModifiers::CONTROL | KeyModifiers::ALT,
+            })
+        );
+        assert_eq!(ctrl_p.to_string(), "Ctrl-p");
+    }
+
+    #[test]
+    fn separators_alone_are_keys() {
+        assert_eq!(KeyBinding::parse("-"), Ok(KeyBinding::char('-')));
+        assert_eq!(KeyBinding::parse("+"), Ok(KeyBinding::char('+')));
+        assert_eq!(
+            KeyBinding::parse("Alt--"),
+            Ok(KeyBinding {
+                code: KeyCode::Char('-'),
+                modifiers: KeyModifiers::ALT,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert!(KeyBinding::parse("Shift-x").is_err());
+        assert!(KeyBinding::parse("Ctrl-").is_err());
+        assert!(KeyBinding::parse("nokey").is_err());
+        assert!(KeyBinding::parse("").is_err());
+    }
+
+    #[test]
+    fn matches_ignoring_shift_but_not_ctrl() {
+        let binding = KeyBinding::char('K');
+        assert!(binding.matches(&key(KeyCode::Char('K'), KeyModifiers::SHIFT)));
+        assert!(!binding.matches(&key(KeyCode::Char('k'), KeyModifiers::NONE)));
+        assert!(!binding.matches(&key(KeyCode::Char('K'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn default_keys_depend_on_the_game_state() {
+        let keymap = KeyMap::default();
+        let enter = key(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.action_for(&enter, &GameState::WaitingStart),
+            Some(Action::Start)
+        );
+        assert_eq!(
+            keymap.action_for(&enter, &GameState::Paused),
+            Some(Action::ResumeNow)
+        );
+        assert_eq!(keymap.label(Action::ToggleLog), "l");
+    }
+
+    #[test]
+    fn coarse_sunray_steps_are_described_with_their_size() {
+        let step = format!("by {}", SUNRAY_COARSE_STEP);
+        assert!(Action::SunrayUpCoarse.description().ends_with(&step));
+        assert!(Action::SunrayDownCoarse.description().ends_with(&step));
+    }
+}
@@ -1,8 +1,9 @@
+use omc_galaxy::Status;
 use omc_galaxy::settings::{self};
 use ratatui::DefaultTerminal;
 use std::time::{Duration, Instant};
 
-use crate::app::App;
+use crate::app::{App, RESUME_COUNTDOWN};
 use crate::game_state::{GameState, handle_game_state};
 use crate::ui::render_ui;
 
@@ -11,6 +12,7 @@ impl App {
         while !self.exit {
             match self.get_game_state() {
                 GameState::WaitingStart => self.waiting_loop(terminal)?,
+                GameState::Editing => self.editor_loop(terminal)?,
                 GameState::Running => self.active_loop(terminal)?,
                 GameState::Paused => self.paused_loop(terminal)?,
                 GameState::Ended => self.end_loop(terminal)?,
@@ -20,12 +22,12 @@ impl App {
     }
 
     fn end_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<(), String> {
-        // Draw the start screen
+        // Draw the end screen
         terminal
             .draw(|frame| render_ui(self, frame))
-            .map_err(|_| "Error while drawing start screen")?;
+            .map_err(|_| "Error while drawing end screen")?;
 
-        // Wait for user input (Start or Quit)
+        // Wait for user input (Restart, Export or Quit)
         handle_game_state(self)?;
         Ok(())
     }
@@ -42,6 +44,16 @@ impl App {
         Ok(())
     }
 
+    /// Loop for the galaxy file editor, the orchestrator is not running yet
+    fn editor_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<(), String> {
+        terminal
+            .draw(|frame| render_ui(self, frame))
+            .map_err(|_| "Error while drawing editor screen")?;
+
+        handle_game_state(self)?;
+        Ok(())
+    }
+
     /// Loop: tick management and orchestrator
     fn active_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<(), String> {
         while !self.exit && self.gamestate == GameState::Running {
@@ -60,11 +72,28 @@ impl App {
             // --- 4. TICK LOGICA (Eventi Spaziali) ---
             if self.last_tick.elapsed() >= self.tick_rate {
                 self.get_game_info();
+                // Scheduled actions of this tick go before its random event
+                self.run_scenario_tick(self.stats.tick + 1);
+                let random_events = self.scenario.as_ref().is_none_or(|s| s.random_events);
+
                 //Questa funzione ritorna un errore se non ci sono più pianeti vivi
-                if self.orchestrator.send_sunray_or_asteroid()
-                    == Err("No more planets alive".to_string())
+                let result = if random_events {
+                    self.orchestrator.send_sunray_or_asteroid()
+                } else if self
+                    .planets_info
+                    .iter()
+                    .all(|(_, info)| matches!(info.status, Status::Dead))
                 {
+                    Err("No more planets alive".to_string())
+                } else {
+                    Ok(())
+                };
+                if result == Err("No more planets alive".to_string()) {
                     self.gamestate = GameState::Ended;
+                    self.stats.finish();
+                } else {
+                    self.stats.record_tick(&self.planets_info, random_events);
+                    self.run_console_hook();
                 }
 
                 self.last_tick = Instant::now();
@@ -77,10 +106,22 @@ impl App {
         Ok(())
     }
 
-    fn get_game_info(&mut self) {
-        self.planets_info = self.orchestrator.get_planets_info();
+    pub(crate) fn get_game_info(&mut self) {
+        let planets_info = self.orchestrator.get_planets_info();
+        let explorers_info = self.orchestrator.get_explorer_states();
+        // Not while loading a galaxy, its planets didn't change during the game
+        if self.gamestate == GameState::Running {
+            self.notifications.record_changes(
+                (&self.planets_info, &planets_info),
+                (&self.explorers_info, &explorers_info),
+                self.stats.tick + 1,
+            );
+        }
+        self.planets_info = planets_info;
+        self.explorers_info = explorers_info;
         self.probability_sunray = settings::get_sunray_probability();
         self.galaxy_topology = self.orchestrator.get_galaxy_topology();
+        self.drop_missing_planet_selection();
     }
 
     /// Pause loop: only consume UI messages, time frozen
@@ -92,6 +133,14 @@ impl App {
 
         // Wait for user input
         handle_game_state(self)?;
+
+        // Resume once the countdown is over
+        if self
+            .resume_countdown
+            .is_some_and(|started| started.elapsed() >= RESUME_COUNTDOWN)
+        {
+            self.resume();
+        }
         Ok(())
     }
 }
@@ -1,40 +1,94 @@
 mod app;
+mod console;
+mod editor;
+mod galaxy_file;
 mod game_state;
+mod generator;
+mod keymap;
 mod loops;
+mod notifications;
+mod palette;
+mod planet_table;
+mod scenario;
+mod stats;
+mod theme;
 mod tui_loggers;
 mod ui;
 
 use std::sync::Arc;
 
 use crate::tui_loggers::LogBuffer;
-use crate::tui_loggers::SimpleTuiLogger;
+use crate::tui_loggers::{SimpleTuiLogger, TuiTracingLayer};
+use crate::generator::GeneratorConfig;
+use crate::scenario::Scenario;
 use app::App;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use omc_galaxy::Orchestrator;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Log entries kept, enough for the entity filter to still find a quiet planet's history
+const LOG_BUFFER_SIZE: usize = 5000;
 
 fn main() -> Result<(), String> {
-    let log_buffer = Arc::new(LogBuffer::new(50)); // Ultimi 50 messaggi
+    // Load env (INPUT_FILE, GALAXY_DIR, KEYMAP_FILE, THEME_FILE)
+    dotenv::dotenv().ok();
+
+    // `generate [options]` writes a galaxy file and exits without starting the TUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("generate") {
+        let (config, output) = GeneratorConfig::from_args(&args[1..])?;
+        generator::generate_file(&config, &output)?;
+        println!("Galaxy written to {}", output);
+        return Ok(());
+    }
+
+    // `--scenario <file>` runs a scenario, it can also be chosen in the start screen
+    let scenario = match args.iter().position(|arg| arg == "--scenario") {
+        Some(i) => {
+            let path = args.get(i + 1).ok_or("--scenario needs a file")?;
+            Some(Scenario::load(path)?)
+        }
+        None => None,
+    };
+
+    // `--accessible` starts in accessibility mode, as do the NO_COLOR and ACCESSIBLE variables
+    let accessible = args.iter().any(|arg| arg == "--accessible");
+
+    let log_buffer = Arc::new(LogBuffer::new(LOG_BUFFER_SIZE));
     let logger = SimpleTuiLogger {
         buffer: Arc::clone(&log_buffer),
     };
 
     log::set_boxed_logger(Box::new(logger)).unwrap();
     log::set_max_level(log::LevelFilter::Info); // Imposta il livello desiderato
-    // Init terminal
+
+    // `tracing` events from the dependencies go to the same buffer, at the same level
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_subscriber::filter::LevelFilter::INFO)
+        .with(TuiTracingLayer {
+            buffer: Arc::clone(&log_buffer),
+        });
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| format!("Cannot set the tracing subscriber: {}", e))?;
+    // Init terminal, with mouse events for clicking and scrolling
     let mut terminal = ratatui::init();
+    crossterm::execute!(std::io::stdout(), EnableMouseCapture)
+        .map_err(|e| format!("Cannot enable mouse capture: {}", e))?;
 
     // Init orchestrator
     let orchestrator = Orchestrator::new()?;
 
     // Create and run game loop
+    // The galaxy is initialized from the start screen, after it can be edited
     let mut app = App::new(orchestrator, log_buffer);
-
-    // Initialize by file
-    app.initialize_by_file()?;
+    app.scenario = scenario;
+    app.accessible |= accessible;
 
     // Start the app
     let result = app.run(&mut terminal);
 
     // Restore terminal
+    let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
     ratatui::restore();
 
     // Return possible error from the app run
@@ -0,0 +1,283 @@
+use std::time::{Duration, Instant};
+
+use omc_galaxy::{PlanetInfoMap, Status, utils::ExplorerInfoMap};
+
+/// Notifications kept in the history, the oldest are dropped first
+const HISTORY_LEN: usize = 200;
+/// Toasts shown at once, the newest ones
+pub const MAX_TOASTS: usize = 4;
+
+/// Game events that raise a notification
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EventKind {
+    PlanetDied,
+    ExplorerDied,
+    RocketBuilt,
+    AsteroidDeflected,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 4] = [
+        EventKind::PlanetDied,
+        EventKind::ExplorerDied,
+        EventKind::RocketBuilt,
+        EventKind::AsteroidDeflected,
+    ];
+
+    /// Name used in the notifications file
+    pub fn name(self) -> &'static str {
+        match self {
+            EventKind::PlanetDied => "planet_died",
+            EventKind::ExplorerDied => "explorer_died",
+            EventKind::RocketBuilt => "rocket_built",
+            EventKind::AsteroidDeflected => "asteroid_deflected",
+        }
+    }
+
+    fn default_severity(self) -> Severity {
+        match self {
+            EventKind::PlanetDied => Severity::Critical,
+            EventKind::ExplorerDied => Severity::Warning,
+            EventKind::RocketBuilt => Severity::Info,
+            EventKind::AsteroidDeflected => Severity::Success,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+/// How much a notification matters, from least to most
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 4] = [
+        Severity::Info,
+        Severity::Success,
+        Severity::Warning,
+        Severity::Critical,
+    ];
+
+    /// Name used in the notifications file
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Success => "success",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+
+    /// Text label, so the severity doesn't rely on colours alone
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "INFO",
+            Severity::Success => "OK",
+            Severity::Warning => "WARNING",
+            Severity::Critical => "CRITICAL",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.name() == name)
+    }
+}
+
+pub struct Notification {
+    pub severity: Severity,
+    pub message: String,
+    /// Tick of the game the event happened in
+    pub tick: u64,
+    pub created: Instant,
+    /// Whether it can be shown as a toast, it always is in the history
+    toast: bool,
+}
+
+/// Whether an event kind is shown as a toast, and how severely
+#[derive(Clone, Copy)]
+struct EventSettings {
+    enabled: bool,
+    severity: Severity,
+}
+
+/// Notifications raised by the game events, shown as toasts for a while and kept in a history
+pub struct Notifications {
+    /// Settings of each event kind, in the order of `EventKind::ALL`
+    settings: [EventSettings; EventKind::ALL.len()],
+    /// How long a toast stays on screen
+    duration: Duration,
+    /// Less severe notifications only go to the history
+    min_severity: Severity,
+    /// Oldest first
+    pub history: Vec<Notification>,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            settings: EventKind::ALL.map(|kind| EventSettings {
+                enabled: true,
+                severity: kind.default_severity(),
+            }),
+            duration: Duration::from_secs(4),
+            min_severity: Severity::Info,
+            history: Vec::new(),
+        }
+    }
+}
+
+impl Notifications {
+    /// Loads the notifications file named by NOTIFICATIONS_FILE (default `notifications.toml`)
+    ///
+    /// `duration` is how many seconds a toast stays, `min_severity` the least
+    /// severe notification shown as a toast, and each `[events.<kind>]` table
+    /// can set `enabled` and `severity`. As for the keymap, problems are
+    /// logged and the faulty entry ignored.
+    pub fn load() -> Self {
+        let path = std::env::var("NOTIFICATIONS_FILE")
+            .unwrap_or_else(|_| "notifications.toml".to_string());
+        let mut notifications = Self::default();
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return notifications;
+        };
+        let table = match content.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => {
+                log::warn!("Ignoring notifications file {}: {}", path, e);
+                return notifications;
+            }
+        };
+
+        if let Some(duration) = table.get("duration") {
+            match duration.as_integer().and_then(|s| u64::try_from(s).ok()) {
+                Some(seconds) => notifications.duration = Duration::from_secs(seconds),
+                None => log::warn!(
+                    "Notifications file {}: `duration` must be a number of seconds",
+                    path
+                ),
+            }
+        }
+        if let Some(name) = table.get("min_severity") {
+            match name.as_str().and_then(Severity::from_name) {
+                Some(severity) => notifications.min_severity = severity,
+                None => log::warn!("Notifications file {}: unknown severity {}", path, name),
+            }
+        }
+
+        if let Some(events) = table.get("events").and_then(|t| t.as_table()) {
+            for (name, event) in events {
+                let Some(kind) = EventKind::from_name(name) else {
+                    log::warn!("Notifications file {}: unknown event `{}`", path, name);
+                    continue;
+                };
+                let settings = &mut notifications.settings[kind as usize];
+                if let Some(enabled) = event.get("enabled") {
+                    match enabled.as_bool() {
+                        Some(enabled) => settings.enabled = enabled,
+                        None => log::warn!(
+                            "Notifications file {}: `{}`: `enabled` must be true or false",
+                            path,
+                            name
+                        ),
+                    }
+                }
+                if let Some(severity) = event.get("severity") {
+                    match severity.as_str().and_then(Severity::from_name) {
+                        Some(severity) => settings.severity = severity,
+                        None => log::warn!(
+                            "Notifications file {}: `{}`: unknown severity {}",
+                            path,
+                            name,
+                            severity
+                        ),
+                    }
+                }
+            }
+        }
+        log::info!("Loaded notifications file {}", path);
+        notifications
+    }
+
+    /// Raises a notification for each event between two snapshots of the galaxy
+    ///
+    /// A planet losing its rocket while staying alive used it on an asteroid.
+    pub fn record_changes(
+        &mut self,
+        planets: (&PlanetInfoMap, &PlanetInfoMap),
+        explorers: (&ExplorerInfoMap, &ExplorerInfoMap),
+        tick: u64,
+    ) {
+        let (old_planets, new_planets) = planets;
+        for (id, info) in new_planets.iter() {
+            let Some(old) = old_planets.get_info(*id) else {
+                continue;
+            };
+            let dead = matches!(info.status, Status::Dead);
+            if dead && !matches!(old.status, Status::Dead) {
+                self.push(EventKind::PlanetDied, format!("Planet {} died", id), tick);
+            } else if !dead && info.rocket && !old.rocket {
+                self.push(
+                    EventKind::RocketBuilt,
+                    format!("Planet {} built a rocket", id),
+                    tick,
+                );
+            } else if !dead && !info.rocket && old.rocket {
+                self.push(
+                    EventKind::AsteroidDeflected,
+                    format!("Planet {} deflected an asteroid", id),
+                    tick,
+                );
+            }
+        }
+
+        let (old_explorers, new_explorers) = explorers;
+        for (id, info) in new_explorers.iter() {
+            let was_alive = old_explorers
+                .get(id)
+                .is_some_and(|old| !matches!(old.status, Status::Dead));
+            if was_alive && matches!(info.status, Status::Dead) {
+                self.push(
+                    EventKind::ExplorerDied,
+                    format!("Explorer {} died on planet {}", id, info.current_planet_id),
+                    tick,
+                );
+            }
+        }
+    }
+
+    /// Records a notification in the history, disabled events don't become toasts
+    fn push(&mut self, kind: EventKind, message: String, tick: u64) {
+        let settings = self.settings[kind as usize];
+        log::info!("{}: {}", settings.severity.label(), message);
+        if self.history.len() == HISTORY_LEN {
+            self.history.remove(0);
+        }
+        self.history.push(Notification {
+            severity: settings.severity,
+            message,
+            tick,
+            created: Instant::now(),
+            toast: settings.enabled,
+        });
+    }
+
+    /// Notifications to show as toasts now, newest first
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.history
+            .iter()
+            .rev()
+            .take_while(|notification| notification.created.elapsed() < self.duration)
+            .filter(|notification| notification.toast && notification.severity >= self.min_severity)
+            .take(MAX_TOASTS)
+    }
+}
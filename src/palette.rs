@@ -0,0 +1,169 @@
+use crate::game_state::GameState;
+use crate::keymap::Action;
+use crate::ui::Tab;
+
+/// Command palette, opened with Ctrl-P, to run any action by typing part of its name
+///
+/// Opened in the game, it only lists the actions about the panels of the
+/// tab it was opened on, until TAB widens it to every action.
+#[derive(Default)]
+pub struct CommandPalette {
+    pub query: String,
+    /// Index in the filtered list
+    pub selected: usize,
+    /// Tab the palette was opened on, `None` outside the game screen
+    pub panel: Option<Tab>,
+    /// Whether the actions of the other panels are listed too
+    pub all_panels: bool,
+}
+
+impl CommandPalette {
+    pub fn new(panel: Option<Tab>) -> Self {
+        Self {
+            panel,
+            ..Self::default()
+        }
+    }
+
+    /// Tab the listed actions are scoped to, if any
+    pub fn scope(&self) -> Option<Tab> {
+        self.panel.filter(|_| !self.all_panels)
+    }
+
+    pub fn toggle_all_panels(&mut self) {
+        self.all_panels = !self.all_panels;
+        self.selected = 0;
+    }
+
+    /// Actions available in `state` and the scoped tab matching the query, best match first
+    pub fn matches(&self, state: &GameState) -> Vec<Action> {
+        let scope = self.scope();
+        let mut scored: Vec<(i32, Action)> = Action::ALL
+            .into_iter()
+            .filter(|action| action.is_available(state) && *action != Action::OpenPalette)
+            .filter(|action| scope.is_none_or(|tab| tab.shows(action.group())))
+            .filter_map(|action| {
+                fuzzy_score(&self.query, &action.description()).map(|score| (score, action))
+            })
+            .collect();
+        // Stable, so equal scores keep the declaration order
+        scored.sort_by_key(|(score, _)| -score);
+        scored.into_iter().map(|(_, action)| action).collect()
+    }
+
+    pub fn selected_action(&self, state: &GameState) -> Option<Action> {
+        self.matches(state).get(self.selected).copied()
+    }
+
+    pub fn move_selection(&mut self, delta: i32, state: &GameState) {
+        let n = self.matches(state).len();
+        if n > 0 {
+            self.selected = (self.selected as i32 + delta).rem_euclid(n as i32) as usize;
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+}
+
+/// Scores `text` against a fuzzy `query`, `None` if the query isn't a subsequence of it
+///
+/// Case is ignored. Consecutive matches and matches at the start of a word
+/// score higher, so "rest" prefers "Restart" to "Reverse Planet Sort".
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + text[position..].iter().position(|c| *c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    // Shorter texts first among equal matches
+    Some(score * 100 - text.len() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_subsequences_match() {
+        assert!(fuzzy_score("sp", "Pause/Resume").is_none());
+        assert!(fuzzy_score("xyz", "Export Report").is_none());
+        assert!(fuzzy_score("", "Quit").is_some());
+    }
+
+    #[test]
+    fn ignores_case_and_spaces_in_the_query() {
+        assert_eq!(
+            fuzzy_score("Next Tab", "Next Tab"),
+            fuzzy_score("nexttab", "next tab")
+        );
+    }
+
+    #[test]
+    fn word_starts_beat_letters_inside_words() {
+        let word_starts = fuzzy_score("sp", "Select Previous Planet").unwrap();
+        let inside = fuzzy_score("sp", "Display").unwrap();
+        assert!(word_starts > inside);
+    }
+
+    #[test]
+    fn consecutive_letters_beat_scattered_ones() {
+        let consecutive = fuzzy_score("rest", "Restart").unwrap();
+        let scattered = fuzzy_score("rest", "Reverse Planet Sort").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn shorter_texts_first_among_equal_matches() {
+        let short = fuzzy_score("help", "Help").unwrap();
+        let long = fuzzy_score("help", "Help Me Please").unwrap();
+        assert!(short > long);
+    }
+
+    #[test]
+    fn lists_the_best_match_first() {
+        let mut palette = CommandPalette::new(None);
+        for c in "next tab".chars() {
+            palette.push(c);
+        }
+        assert_eq!(
+            palette.matches(&GameState::Running).first(),
+            Some(&Action::NextTab)
+        );
+    }
+
+    #[test]
+    fn scopes_to_the_tab_until_widened() {
+        let mut palette = CommandPalette::new(Some(Tab::Explorers));
+        for c in "select next".chars() {
+            palette.push(c);
+        }
+        let scoped = palette.matches(&GameState::Running);
+        assert!(scoped.contains(&Action::SelectNextExplorer));
+        assert!(!scoped.contains(&Action::SelectNextPlanet));
+
+        palette.toggle_all_panels();
+        let all = palette.matches(&GameState::Running);
+        assert!(all.contains(&Action::SelectNextPlanet));
+        assert!(palette.scope().is_none());
+    }
+}
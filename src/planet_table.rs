@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+
+use omc_galaxy::{PlanetInfoMap, Status};
+
+/// Column the planets table is sorted by
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SortKey {
+    #[default]
+    Id,
+    Energy,
+    Rocket,
+    Status,
+    Neighbours,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 5] = [
+        SortKey::Id,
+        SortKey::Energy,
+        SortKey::Rocket,
+        SortKey::Status,
+        SortKey::Neighbours,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SortKey::Id => "ID",
+            SortKey::Energy => "Energy",
+            SortKey::Rocket => "Rocket",
+            SortKey::Status => "Status",
+            SortKey::Neighbours => "Links",
+        }
+    }
+
+    fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|key| *key == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Planets shown in the planets table
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PlanetFilter {
+    #[default]
+    All,
+    Alive,
+    /// The given planet and the ones linked to it
+    NeighboursOf(u32),
+    HasRocket,
+    /// Less than half of the energy cells charged
+    LowEnergy,
+}
+
+impl PlanetFilter {
+    pub fn title(self) -> String {
+        match self {
+            PlanetFilter::All => "all".to_string(),
+            PlanetFilter::Alive => "alive only".to_string(),
+            PlanetFilter::NeighboursOf(id) => format!("linked to {}", id),
+            PlanetFilter::HasRocket => "with rocket".to_string(),
+            PlanetFilter::LowEnergy => "low energy".to_string(),
+        }
+    }
+
+    /// The filter after this one; the neighbours filter needs a `selected` planet
+    fn next(self, selected: Option<u32>) -> Self {
+        match (self, selected) {
+            (PlanetFilter::All, _) => PlanetFilter::Alive,
+            (PlanetFilter::Alive, Some(id)) => PlanetFilter::NeighboursOf(id),
+            (PlanetFilter::Alive, None) | (PlanetFilter::NeighboursOf(_), _) => {
+                PlanetFilter::HasRocket
+            }
+            (PlanetFilter::HasRocket, _) => PlanetFilter::LowEnergy,
+            (PlanetFilter::LowEnergy, _) => PlanetFilter::All,
+        }
+    }
+}
+
+/// Order and filter of the planets table
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlanetView {
+    pub sort: SortKey,
+    pub descending: bool,
+    pub filter: PlanetFilter,
+}
+
+impl PlanetView {
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+    }
+
+    pub fn reverse(&mut self) {
+        self.descending = !self.descending;
+    }
+
+    pub fn cycle_filter(&mut self, selected: Option<u32>) {
+        self.filter = self.filter.next(selected);
+    }
+
+    /// IDs of the planets to show, in table order
+    ///
+    /// Ties are broken by ID, so rows don't swap places between ticks.
+    pub fn rows(&self, planets: &PlanetInfoMap, topology: &[Vec<bool>]) -> Vec<u32> {
+        let linked = |a: u32, b: u32| {
+            topology
+                .get(a as usize)
+                .and_then(|row| row.get(b as usize))
+                .copied()
+                .unwrap_or(false)
+        };
+        let neighbours = |id: u32| {
+            topology
+                .get(id as usize)
+                .map_or(0, |row| row.iter().filter(|linked| **linked).count())
+        };
+
+        let mut rows: Vec<_> = planets
+            .iter()
+            .filter(|(id, info)| match self.filter {
+                PlanetFilter::All => true,
+                PlanetFilter::Alive => !matches!(info.status, Status::Dead),
+                PlanetFilter::NeighboursOf(selected) => **id == selected || linked(selected, **id),
+                PlanetFilter::HasRocket => info.rocket,
+                PlanetFilter::LowEnergy => info.charged_cells_count * 2 < info.energy_cells.len(),
+            })
+            .collect();
+
+        rows.sort_by(|(a, a_info), (b, b_info)| {
+            let order = match self.sort {
+                SortKey::Id => Ordering::Equal,
+                SortKey::Energy => a_info.charged_cells_count.cmp(&b_info.charged_cells_count),
+                SortKey::Rocket => a_info.rocket.cmp(&b_info.rocket),
+                SortKey::Status => status_rank(&a_info.status).cmp(&status_rank(&b_info.status)),
+                SortKey::Neighbours => neighbours(**a).cmp(&neighbours(**b)),
+            };
+            let order = order.then(a.cmp(b));
+            if self.descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+        rows.into_iter().map(|(id, _)| *id).collect()
+    }
+}
+
+/// Position of a status when sorting, from the most to the least alive
+fn status_rank(status: &Status) -> u8 {
+    match status {
+        Status::Running => 0,
+        Status::Paused => 1,
+        Status::Dead => 2,
+    }
+}
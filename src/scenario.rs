@@ -0,0 +1,279 @@
+use std::fmt;
+
+/// Something a scenario does at a given tick
+///
+/// Only what the orchestrator offers: it sends events with
+/// `send_sunray_or_asteroid`, picking the kind by the sunray probability,
+/// and can't aim them at a planet.
+#[derive(Clone, Debug)]
+pub enum ScenarioAction {
+    SetSunray(u32),
+    /// One more sunray or asteroid, on top of the random stream
+    RandomEvent,
+    Pause,
+}
+
+impl fmt::Display for ScenarioAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioAction::SetSunray(probability) => write!(f, "sunray {}%", probability),
+            ScenarioAction::RandomEvent => write!(f, "random event"),
+            ScenarioAction::Pause => write!(f, "pause"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ScheduledAction {
+    pub tick: u64,
+    pub action: ScenarioAction,
+}
+
+/// A list of actions scheduled at given ticks, read from a TOML file
+///
+/// ```toml
+/// name = "Asteroid storm"
+/// random_events = true   # keep the random sunray/asteroid stream (default)
+///
+/// [[event]]
+/// tick = 10
+/// action = "set_sunray"  # set_sunray, random_event, pause
+/// probability = 0
+///
+/// [[event]]
+/// tick = 15
+/// action = "random_event"
+/// ```
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    pub path: String,
+    pub name: String,
+    /// Whether the random event stream keeps running next to the scenario
+    pub random_events: bool,
+    /// Sorted by tick, actions of the same tick keep the file order
+    pub actions: Vec<ScheduledAction>,
+    /// Index of the first action not executed yet
+    next: usize,
+}
+
+impl Scenario {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path, e))?;
+        Self::parse(path, &content).map_err(|e| format!("Invalid scenario {}: {}", path, e))
+    }
+
+    pub fn parse(path: &str, content: &str) -> Result<Self, String> {
+        let table = content.parse::<toml::Table>().map_err(|e| e.to_string())?;
+
+        let name = match table.get("name") {
+            Some(name) => name.as_str().ok_or("`name` must be a string")?.to_string(),
+            None => path.to_string(),
+        };
+        let random_events = match table.get("random_events") {
+            Some(value) => value
+                .as_bool()
+                .ok_or("`random_events` must be true or false")?,
+            None => true,
+        };
+
+        let events = match table.get("event") {
+            Some(events) => events
+                .as_array()
+                .ok_or("`event` must be a list of [[event]] tables")?
+                .as_slice(),
+            None => &[],
+        };
+        let mut actions = events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| {
+                let event = event
+                    .as_table()
+                    .ok_or_else(|| format!("event {}: not a table", i + 1))?;
+                Self::parse_event(event).map_err(|e| format!("event {}: {}", i + 1, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        actions.sort_by_key(|scheduled| scheduled.tick);
+
+        Ok(Self {
+            path: path.to_string(),
+            name,
+            random_events,
+            actions,
+            next: 0,
+        })
+    }
+
+    fn parse_event(event: &toml::Table) -> Result<ScheduledAction, String> {
+        let number = |key: &str| {
+            event
+                .get(key)
+                .ok_or_else(|| format!("missing `{}`", key))?
+                .as_integer()
+                .and_then(|n| u32::try_from(n).ok())
+                .ok_or_else(|| format!("`{}` must be a number, 0 or more", key))
+        };
+
+        let tick = number("tick")? as u64;
+        let action = match event.get("action").and_then(|a| a.as_str()) {
+            Some("set_sunray") => {
+                let probability = number("probability")?;
+                if probability > 100 {
+                    return Err("`probability` must be between 0 and 100".to_string());
+                }
+                ScenarioAction::SetSunray(probability)
+            }
+            Some("random_event") => ScenarioAction::RandomEvent,
+            Some("pause") => ScenarioAction::Pause,
+            Some(other @ ("sunray" | "asteroid" | "kill_explorer")) => {
+                return Err(format!(
+                    "`{}` is not supported, the orchestrator can't aim events or kill explorers",
+                    other
+                ));
+            }
+            Some(other) => return Err(format!("unknown action `{}`", other)),
+            None => return Err("missing `action`".to_string()),
+        };
+        Ok(ScheduledAction { tick, action })
+    }
+
+    /// Actions due at or before `tick` that were not executed yet, marked as executed
+    pub fn take_due(&mut self, tick: u64) -> Vec<ScenarioAction> {
+        let due = self.actions[self.next..]
+            .iter()
+            .take_while(|scheduled| scheduled.tick <= tick)
+            .map(|scheduled| scheduled.action.clone())
+            .collect::<Vec<_>>();
+        self.next += due.len();
+        due
+    }
+
+    /// Actions not executed yet, in order
+    pub fn upcoming(&self) -> &[ScheduledAction] {
+        &self.actions[self.next..]
+    }
+
+    pub fn executed(&self) -> usize {
+        self.next
+    }
+
+    /// Makes the scenario ready to run again from tick 0
+    pub fn rewind(&mut self) {
+        self.next = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(actions: &[ScenarioAction]) -> Vec<String> {
+        actions.iter().map(|action| action.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_events_sorted_by_tick() {
+        let scenario = Scenario::parse(
+            "storm.toml",
+            r#"
+            name = "Storm"
+            random_events = false
+
+            [[event]]
+            tick = 5
+            action = "random_event"
+
+            [[event]]
+            tick = 0
+            action = "set_sunray"
+            probability = 0
+
+            [[event]]
+            tick = 5
+            action = "pause"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(scenario.name, "Storm");
+        assert!(!scenario.random_events);
+        let ticks: Vec<u64> = scenario.actions.iter().map(|s| s.tick).collect();
+        assert_eq!(ticks, [0, 5, 5]);
+        // Same tick, file order
+        assert_eq!(scenario.actions[2].action.to_string(), "pause");
+    }
+
+    #[test]
+    fn defaults_to_the_path_and_random_events() {
+        let scenario = Scenario::parse("empty.toml", "").unwrap();
+        assert_eq!(scenario.name, "empty.toml");
+        assert!(scenario.random_events);
+        assert!(scenario.actions.is_empty());
+    }
+
+    #[test]
+    fn reports_the_broken_event() {
+        let error = |content: &str| Scenario::parse("s.toml", content).unwrap_err();
+        assert_eq!(
+            error("[[event]]\naction = \"pause\"\n"),
+            "event 1: missing `tick`"
+        );
+        assert_eq!(
+            error("[[event]]\ntick = 1\naction = \"asteroid\"\nplanet = 2\n"),
+            "event 1: `asteroid` is not supported, the orchestrator can't aim events or kill explorers"
+        );
+        assert_eq!(
+            error("[[event]]\ntick = -1\naction = \"pause\"\n"),
+            "event 1: `tick` must be a number, 0 or more"
+        );
+        assert_eq!(
+            error("[[event]]\ntick = 1\naction = \"set_sunray\"\nprobability = 101\n"),
+            "event 1: `probability` must be between 0 and 100"
+        );
+        assert_eq!(
+            error(
+                "[[event]]\ntick = 1\naction = \"pause\"\n[[event]]\ntick = 2\naction = \"nova\"\n"
+            ),
+            "event 2: unknown action `nova`"
+        );
+    }
+
+    #[test]
+    fn takes_each_action_once_when_due() {
+        let mut scenario = Scenario::parse(
+            "s.toml",
+            r#"
+            [[event]]
+            tick = 2
+            action = "random_event"
+
+            [[event]]
+            tick = 4
+            action = "pause"
+
+            [[event]]
+            tick = 4
+            action = "set_sunray"
+            probability = 80
+            "#,
+        )
+        .unwrap();
+        assert!(scenario.take_due(1).is_empty());
+        assert_eq!(names(&scenario.take_due(2)), ["random event"]);
+        assert!(scenario.take_due(2).is_empty());
+        // Skipped ticks still run what they had due
+        assert_eq!(names(&scenario.take_due(10)), ["pause", "sunray 80%"]);
+        assert_eq!(scenario.executed(), 3);
+        assert!(scenario.upcoming().is_empty());
+
+        scenario.rewind();
+        assert_eq!(scenario.upcoming().len(), 3);
+    }
+
+    #[test]
+    fn the_example_file_parses() {
+        let content = include_str!("../scenario.example.toml");
+        let scenario = Scenario::parse("scenario.example.toml", content).unwrap();
+        assert!(!scenario.actions.is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+
+use omc_galaxy::{PlanetInfoMap, Status, utils::ExplorerInfoMap};
+
+/// Statistics collected tick by tick, shown in the end screen
+#[derive(Default)]
+pub struct GameStats {
+    /// Ticks elapsed since the game started
+    pub tick: u64,
+    pub started_at: Option<Instant>,
+    pub ended_at: Option<Instant>,
+    /// Planets in the order they died, with the tick of death
+    pub planet_deaths: Vec<(u32, u64)>,
+    /// Events sent by the random stream, a scenario or the console, the orchestrator picks sunray or asteroid
+    pub random_events: u64,
+    /// Total charged energy cells, highest value seen and last value
+    pub peak_energy: usize,
+    pub final_energy: usize,
+    /// Alive planets at each tick, index 0 is the start of the game
+    pub alive_history: Vec<usize>,
+    /// Total charged energy cells at each tick
+    pub energy_history: Vec<usize>,
+    /// State and history of each energy cell, by planet
+    pub cells: BTreeMap<u32, Vec<CellStats>>,
+}
+
+/// What happened to an energy cell during the game
+#[derive(Clone, Copy, Default)]
+pub struct CellStats {
+    pub charged: bool,
+    /// Tick the cell was last charged at, 0 if it was charged from the start
+    pub last_charged: Option<u64>,
+    pub charges: u32,
+    pub discharges: u32,
+}
+
+impl GameStats {
+    pub fn start(&mut self, planets: &PlanetInfoMap) {
+        *self = Self {
+            started_at: Some(Instant::now()),
+            ..Self::default()
+        };
+        self.record_planets(planets);
+    }
+
+    pub fn finish(&mut self) {
+        if self.ended_at.is_none() {
+            self.ended_at = Some(Instant::now());
+        }
+    }
+
+    /// Records a tick of the simulation, `random_event` if the random stream sent one
+    pub fn record_tick(&mut self, planets: &PlanetInfoMap, random_event: bool) {
+        self.tick += 1;
+        if random_event {
+            self.random_events += 1;
+        }
+        self.record_planets(planets);
+    }
+
+    fn record_planets(&mut self, planets: &PlanetInfoMap) {
+        let dead: BTreeSet<u32> = self.planet_deaths.iter().map(|(id, _)| *id).collect();
+        for (id, info) in planets.iter() {
+            if matches!(info.status, Status::Dead) && !dead.contains(id) {
+                self.planet_deaths.push((*id, self.tick));
+            }
+
+            let cells = self.cells.entry(*id).or_default();
+            cells.resize(info.energy_cells.len(), CellStats::default());
+            for (cell, charged) in cells.iter_mut().zip(&info.energy_cells) {
+                if *charged && !cell.charged {
+                    cell.last_charged = Some(self.tick);
+                    // Cells charged from the start don't count as charged during the game
+                    if self.tick > 0 {
+                        cell.charges += 1;
+                    }
+                } else if !*charged && cell.charged {
+                    cell.discharges += 1;
+                }
+                cell.charged = *charged;
+            }
+        }
+
+        let energy = planets
+            .iter()
+            .map(|(_, info)| info.charged_cells_count)
+            .sum();
+        self.peak_energy = self.peak_energy.max(energy);
+        self.final_energy = energy;
+        self.energy_history.push(energy);
+
+        let alive = planets
+            .iter()
+            .filter(|(_, info)| !matches!(info.status, Status::Dead))
+            .count();
+        self.alive_history.push(alive);
+    }
+
+    /// Wall-clock time between the start and the end of the game (or now)
+    pub fn duration(&self) -> Duration {
+        match self.started_at {
+            Some(start) => self.ended_at.unwrap_or_else(Instant::now) - start,
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Plain text version of the end screen, for exporting
+    pub fn report(&self, explorers: &ExplorerInfoMap) -> String {
+        let duration = self.duration().as_secs();
+        let mut out = String::from("One Million Crabs Galaxy - game report\n\n");
+
+        out.push_str(&format!(
+            "Survived: {} ticks ({}m {:02}s)\n",
+            self.tick,
+            duration / 60,
+            duration % 60
+        ));
+        out.push_str(&format!(
+            "Random events: {} (sunray or asteroid, picked by the orchestrator)\n",
+            self.random_events
+        ));
+        out.push_str(&format!(
+            "Energy: peak {} charged cells, final {}\n\n",
+            self.peak_energy, self.final_energy
+        ));
+
+        out.push_str("Planet deaths:\n");
+        for (order, (id, tick)) in self.planet_deaths.iter().enumerate() {
+            out.push_str(&format!(
+                "  {}. planet {} at tick {}\n",
+                order + 1,
+                id,
+                tick
+            ));
+        }
+
+        out.push_str("\nExplorers:\n");
+        for (id, info) in explorers.iter() {
+            let state = if matches!(info.status, Status::Dead) {
+                "dead"
+            } else {
+                "survived"
+            };
+            out.push_str(&format!("  explorer {}: {}\n", id, state));
+        }
+
+        out.push_str("\nAlive planets per tick:\n");
+        let history: Vec<String> = self.alive_history.iter().map(|n| n.to_string()).collect();
+        out.push_str(&history.join(","));
+        out.push('\n');
+        out
+    }
+}
@@ -0,0 +1,388 @@
+use log::Level;
+use omc_galaxy::Status;
+use ratatui::style::Color;
+
+use crate::notifications::Severity;
+
+/// Colours of the UI by role, so that the whole interface changes scheme at once
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    /// Painted behind everything, `reset` keeps the terminal background
+    pub background: Color,
+    /// Values and plain text
+    pub text: Color,
+    /// Labels in front of the values
+    pub label: Color,
+    /// Hints, disabled entries and secondary lines
+    pub muted: Color,
+    /// Borders of the panels
+    pub border: Color,
+    /// Titles and highlighted information
+    pub accent: Color,
+    /// Table headers and section titles
+    pub header: Color,
+    /// Selected row of the tables and lists
+    pub highlight_fg: Color,
+    pub highlight_bg: Color,
+    /// Strongly selected items: palette entry, map node, form field
+    pub selected_fg: Color,
+    pub selected_bg: Color,
+    /// Status of planets and explorers
+    pub alive: Color,
+    pub dead: Color,
+    pub paused: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    /// Generator, sunray controls and the like
+    pub special: Color,
+    /// Sunray gauge
+    pub sunray: Color,
+    /// Charged and empty energy cells of the planets
+    pub energy_charged: Color,
+    pub energy_empty: Color,
+    pub log_error: Color,
+    pub log_warn: Color,
+    pub log_info: Color,
+    pub log_debug: Color,
+    pub log_trace: Color,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        background: Color::Reset,
+        text: Color::White,
+        label: Color::Gray,
+        muted: Color::DarkGray,
+        border: Color::Blue,
+        accent: Color::Cyan,
+        header: Color::Yellow,
+        highlight_fg: Color::White,
+        highlight_bg: Color::DarkGray,
+        selected_fg: Color::Black,
+        selected_bg: Color::Green,
+        alive: Color::Green,
+        dead: Color::Red,
+        paused: Color::Yellow,
+        success: Color::Green,
+        warning: Color::Yellow,
+        error: Color::Red,
+        special: Color::Magenta,
+        sunray: Color::Yellow,
+        energy_charged: Color::Yellow,
+        energy_empty: Color::DarkGray,
+        log_error: Color::Red,
+        log_warn: Color::Yellow,
+        log_info: Color::Green,
+        log_debug: Color::Cyan,
+        log_trace: Color::DarkGray,
+    };
+
+    /// For light terminal backgrounds, paints its own white background
+    pub const LIGHT: Theme = Theme {
+        background: Color::White,
+        text: Color::Black,
+        label: Color::DarkGray,
+        muted: Color::Gray,
+        border: Color::DarkGray,
+        accent: Color::Blue,
+        header: Color::Magenta,
+        highlight_fg: Color::Black,
+        highlight_bg: Color::Indexed(153),
+        selected_fg: Color::White,
+        selected_bg: Color::Blue,
+        alive: Color::Indexed(28),
+        dead: Color::Red,
+        paused: Color::Indexed(130),
+        success: Color::Indexed(28),
+        warning: Color::Indexed(130),
+        error: Color::Red,
+        special: Color::Magenta,
+        sunray: Color::Indexed(172),
+        energy_charged: Color::Indexed(130),
+        energy_empty: Color::Gray,
+        log_error: Color::Red,
+        log_warn: Color::Indexed(130),
+        log_info: Color::Indexed(28),
+        log_debug: Color::Blue,
+        log_trace: Color::Gray,
+    };
+
+    /// Bright colours on black, no dim text
+    pub const HIGH_CONTRAST: Theme = Theme {
+        background: Color::Black,
+        text: Color::White,
+        label: Color::White,
+        muted: Color::Gray,
+        border: Color::White,
+        accent: Color::LightCyan,
+        header: Color::LightYellow,
+        highlight_fg: Color::Black,
+        highlight_bg: Color::White,
+        selected_fg: Color::Black,
+        selected_bg: Color::LightYellow,
+        alive: Color::LightGreen,
+        dead: Color::LightRed,
+        paused: Color::LightYellow,
+        success: Color::LightGreen,
+        warning: Color::LightYellow,
+        error: Color::LightRed,
+        special: Color::LightMagenta,
+        sunray: Color::LightYellow,
+        energy_charged: Color::LightYellow,
+        energy_empty: Color::Gray,
+        log_error: Color::LightRed,
+        log_warn: Color::LightYellow,
+        log_info: Color::LightGreen,
+        log_debug: Color::LightCyan,
+        log_trace: Color::Gray,
+    };
+
+    /// Okabe-Ito palette: alive and dead never rely on telling red from green
+    pub const COLOUR_BLIND: Theme = Theme {
+        background: Color::Reset,
+        text: Color::White,
+        label: Color::Gray,
+        muted: Color::DarkGray,
+        border: Color::Rgb(0, 114, 178),
+        accent: Color::Rgb(86, 180, 233),
+        header: Color::Rgb(240, 228, 66),
+        highlight_fg: Color::White,
+        highlight_bg: Color::DarkGray,
+        selected_fg: Color::Black,
+        selected_bg: Color::Rgb(86, 180, 233),
+        alive: Color::Rgb(86, 180, 233),
+        dead: Color::Rgb(213, 94, 0),
+        paused: Color::Rgb(240, 228, 66),
+        success: Color::Rgb(0, 158, 115),
+        warning: Color::Rgb(230, 159, 0),
+        error: Color::Rgb(213, 94, 0),
+        special: Color::Rgb(204, 121, 167),
+        sunray: Color::Rgb(240, 228, 66),
+        energy_charged: Color::Rgb(240, 228, 66),
+        energy_empty: Color::DarkGray,
+        log_error: Color::Rgb(213, 94, 0),
+        log_warn: Color::Rgb(230, 159, 0),
+        log_info: Color::Rgb(0, 158, 115),
+        log_debug: Color::Rgb(86, 180, 233),
+        log_trace: Color::DarkGray,
+    };
+
+    /// Used instead of every other theme when NO_COLOR is set
+    pub const MONOCHROME: Theme = Theme {
+        background: Color::Reset,
+        text: Color::Reset,
+        label: Color::Reset,
+        muted: Color::Reset,
+        border: Color::Reset,
+        accent: Color::Reset,
+        header: Color::Reset,
+        highlight_fg: Color::Reset,
+        highlight_bg: Color::Reset,
+        selected_fg: Color::Reset,
+        selected_bg: Color::Reset,
+        alive: Color::Reset,
+        dead: Color::Reset,
+        paused: Color::Reset,
+        success: Color::Reset,
+        warning: Color::Reset,
+        error: Color::Reset,
+        special: Color::Reset,
+        sunray: Color::Reset,
+        energy_charged: Color::Reset,
+        energy_empty: Color::Reset,
+        log_error: Color::Reset,
+        log_warn: Color::Reset,
+        log_info: Color::Reset,
+        log_debug: Color::Reset,
+        log_trace: Color::Reset,
+    };
+
+    pub const BUILT_IN: [(&'static str, Theme); 4] = [
+        ("dark", Theme::DARK),
+        ("light", Theme::LIGHT),
+        ("high-contrast", Theme::HIGH_CONTRAST),
+        ("colour-blind", Theme::COLOUR_BLIND),
+    ];
+
+    /// Colour of a role by the name used in the theme file
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        let color = match role {
+            "background" => &mut self.background,
+            "text" => &mut self.text,
+            "label" => &mut self.label,
+            "muted" => &mut self.muted,
+            "border" => &mut self.border,
+            "accent" => &mut self.accent,
+            "header" => &mut self.header,
+            "highlight_fg" => &mut self.highlight_fg,
+            "highlight_bg" => &mut self.highlight_bg,
+            "selected_fg" => &mut self.selected_fg,
+            "selected_bg" => &mut self.selected_bg,
+            "alive" => &mut self.alive,
+            "dead" => &mut self.dead,
+            "paused" => &mut self.paused,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "special" => &mut self.special,
+            "sunray" => &mut self.sunray,
+            "energy_charged" => &mut self.energy_charged,
+            "energy_empty" => &mut self.energy_empty,
+            "log_error" => &mut self.log_error,
+            "log_warn" => &mut self.log_warn,
+            "log_info" => &mut self.log_info,
+            "log_debug" => &mut self.log_debug,
+            "log_trace" => &mut self.log_trace,
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    pub fn log_level(&self, level: Level) -> Color {
+        match level {
+            Level::Error => self.log_error,
+            Level::Warn => self.log_warn,
+            Level::Info => self.log_info,
+            Level::Debug => self.log_debug,
+            Level::Trace => self.log_trace,
+        }
+    }
+
+    /// Colour of the toasts and history entries of a notification
+    pub fn severity(&self, severity: Severity) -> Color {
+        match severity {
+            Severity::Info => self.accent,
+            Severity::Success => self.success,
+            Severity::Warning => self.warning,
+            Severity::Critical => self.error,
+        }
+    }
+
+    /// Colour of a planet or explorer status
+    pub fn status(&self, status: &Status) -> Color {
+        match status {
+            Status::Running => self.alive,
+            Status::Paused => self.paused,
+            Status::Dead => self.dead,
+        }
+    }
+
+    /// Reads a user theme: a `base` built-in theme and the roles it changes
+    fn parse(table: &toml::Table) -> Result<Self, String> {
+        let base = match table.get("base") {
+            Some(base) => base.as_str().ok_or("`base` must be a string")?,
+            None => "dark",
+        };
+        let mut theme = Self::BUILT_IN
+            .iter()
+            .find(|(name, _)| *name == base)
+            .map(|(_, theme)| *theme)
+            .ok_or_else(|| format!("unknown base theme `{}`", base))?;
+
+        for (role, value) in table.iter().filter(|(key, _)| *key != "base") {
+            let color = value
+                .as_str()
+                .ok_or_else(|| format!("`{}` must be a string", role))?
+                .parse::<Color>()
+                .map_err(|_| format!("`{}`: unknown colour {}", role, value))?;
+            *theme
+                .role_mut(role)
+                .ok_or_else(|| format!("unknown role `{}`", role))? = color;
+        }
+        Ok(theme)
+    }
+}
+
+/// Whether the NO_COLOR convention asks for no colours (https://no-color.org)
+pub fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// The built-in themes followed by the user ones, and the theme in use
+pub struct Themes {
+    themes: Vec<(String, Theme)>,
+    current: usize,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self {
+            themes: Theme::BUILT_IN
+                .iter()
+                .map(|(name, theme)| (name.to_string(), *theme))
+                .collect(),
+            current: 0,
+        }
+    }
+}
+
+impl Themes {
+    /// Loads the theme file named by THEME_FILE (default `theme.toml`)
+    ///
+    /// Each `[themes.<name>]` table takes a `base` built-in theme and the
+    /// roles it changes, e.g. `border = "magenta"` or `accent = "#ff8800"`.
+    /// A top-level `theme = "<name>"` picks the theme used at start. As for
+    /// the keymap, problems are logged and the faulty theme ignored.
+    /// With NO_COLOR set, the only theme is the monochrome one.
+    pub fn load() -> Self {
+        if no_color() {
+            return Self {
+                themes: vec![("monochrome".to_string(), Theme::MONOCHROME)],
+                current: 0,
+            };
+        }
+
+        let path = std::env::var("THEME_FILE").unwrap_or_else(|_| "theme.toml".to_string());
+        let mut themes = Self::default();
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return themes;
+        };
+        let table = match content.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => {
+                log::warn!("Ignoring theme file {}: {}", path, e);
+                return themes;
+            }
+        };
+
+        if let Some(user_themes) = table.get("themes").and_then(|t| t.as_table()) {
+            for (name, theme) in user_themes {
+                let theme = theme
+                    .as_table()
+                    .ok_or_else(|| "expected a table".to_string())
+                    .and_then(Theme::parse);
+                match theme {
+                    Ok(theme) => match themes.themes.iter_mut().find(|(n, _)| n == name) {
+                        Some((_, existing)) => *existing = theme,
+                        None => themes.themes.push((name.clone(), theme)),
+                    },
+                    Err(e) => log::warn!("Theme file {}: `{}`: {}", path, name, e),
+                }
+            }
+        }
+
+        if let Some(name) = table.get("theme").and_then(|t| t.as_str()) {
+            match themes.themes.iter().position(|(n, _)| n == name) {
+                Some(i) => themes.current = i,
+                None => log::warn!("Theme file {}: unknown theme `{}`", path, name),
+            }
+        }
+        log::info!("Loaded theme file {}", path);
+        themes
+    }
+
+    pub fn current(&self) -> Theme {
+        self.themes[self.current].1
+    }
+
+    pub fn current_name(&self) -> &str {
+        &self.themes[self.current].0
+    }
+
+    /// Switches to the next theme, back to the first after the last one
+    pub fn cycle(&mut self) {
+        self.current = (self.current + 1) % self.themes.len();
+    }
+}
@@ -1,11 +1,203 @@
+use log::kv::{self, Key, Value, VisitSource};
 use log::{Log, Metadata, Record};
 use std::collections::VecDeque;
+use std::fmt;
 use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record as SpanRecord};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Planet or explorer a log entry is about
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Entity {
+    Planet(u32),
+    Explorer(u32),
+}
+
+impl Entity {
+    /// Reads `planet 3`, `planet_3`, `planet-3` or `planet3` (explorers alike) in lowercase text
+    fn parse(text: &str) -> Option<Self> {
+        let (kind, rest) = if let Some(rest) = text.strip_prefix("planet") {
+            (Entity::Planet as fn(u32) -> Self, rest)
+        } else if let Some(rest) = text.strip_prefix("explorer") {
+            (Entity::Explorer as fn(u32) -> Self, rest)
+        } else {
+            return None;
+        };
+        let rest = rest.trim_start_matches([' ', '_', '-', ':']);
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        rest[..digits].parse().ok().map(kind)
+    }
+
+    /// Every entity named in a target like `omc_galaxy::planet::3` or a message like
+    /// "Explorer 0 died on planet 2", in order
+    fn find_all(text: &str) -> Vec<Self> {
+        // ASCII lowercase keeps the byte offsets of the original text
+        let text = text.to_ascii_lowercase();
+        let mut entities = Vec::new();
+        let mut previous: Option<char> = None;
+        for (i, c) in text.char_indices() {
+            // Only at the start of a word, so that "subplanet 3" doesn't count
+            let word_start = previous.is_none_or(|c| !c.is_alphanumeric());
+            previous = Some(c);
+            if word_start
+                && let Some(entity) = Self::parse(&text[i..])
+                && !entities.contains(&entity)
+            {
+                entities.push(entity);
+            }
+        }
+        entities
+    }
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entity::Planet(id) => write!(f, "planet {}", id),
+            Entity::Explorer(id) => write!(f, "explorer {}", id),
+        }
+    }
+}
+
+/// Value of a structured key-value field, typed as logged
+#[derive(Clone, PartialEq, Debug)]
+pub enum FieldValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl FieldValue {
+    fn from_kv(value: &Value) -> Self {
+        if let Some(value) = value.to_bool() {
+            FieldValue::Bool(value)
+        } else if let Some(value) = value.to_i64() {
+            FieldValue::Int(value)
+        } else if let Some(value) = value.to_f64() {
+            FieldValue::Float(value)
+        } else {
+            // Strings and anything else, as they would be printed
+            FieldValue::Str(value.to_string())
+        }
+    }
+
+    /// The value as an ID, if it is a non-negative integer
+    pub fn as_id(&self) -> Option<u32> {
+        match self {
+            FieldValue::Int(value) => u32::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Bool(value) => write!(f, "{}", value),
+            FieldValue::Int(value) => write!(f, "{}", value),
+            FieldValue::Float(value) => write!(f, "{}", value),
+            FieldValue::Str(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Collects the key-values of a record, in the order they were logged
+#[derive(Default)]
+struct FieldCollector(Vec<(String, FieldValue)>);
+
+impl<'kvs> VisitSource<'kvs> for FieldCollector {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.push((key.to_string(), FieldValue::from_kv(&value)));
+        Ok(())
+    }
+}
+
+/// A log message with its structured fields and the planets and explorers it concerns
+pub struct LogEntry {
+    pub level: log::Level,
+    pub message: String,
+    /// Key-values logged with the message, e.g. `msg_type = "Sunray"`, `source = 3`
+    pub fields: Vec<(String, FieldValue)>,
+    /// Named in the target first, then by the fields, then in the message
+    pub entities: Vec<Entity>,
+}
+
+impl LogEntry {
+    pub fn new(
+        level: log::Level,
+        target: &str,
+        message: String,
+        fields: Vec<(String, FieldValue)>,
+    ) -> Self {
+        // A field is about an entity if its key names one, as `planet_id` or `source_planet`,
+        // counts such as `planets_alive` are not
+        let names_kind = |key: &str, kind: &str| {
+            let key = key.strip_suffix("_id").unwrap_or(key);
+            key == kind
+                || key
+                    .strip_suffix(kind)
+                    .is_some_and(|rest| rest.ends_with('_'))
+        };
+        let field_entities = fields.iter().filter_map(|(key, value)| {
+            let id = value.as_id()?;
+            if names_kind(key, "planet") {
+                Some(Entity::Planet(id))
+            } else if names_kind(key, "explorer") {
+                Some(Entity::Explorer(id))
+            } else {
+                None
+            }
+        });
+
+        let mut entities = Vec::new();
+        for entity in Entity::find_all(target)
+            .into_iter()
+            .chain(field_entities)
+            .chain(Entity::find_all(&message))
+        {
+            if !entities.contains(&entity) {
+                entities.push(entity);
+            }
+        }
+        Self {
+            level,
+            message,
+            fields,
+            entities,
+        }
+    }
+
+    pub fn concerns(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    pub fn field(&self, key: &str) -> Option<&FieldValue> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == key)
+            .map(|(_, value)| value)
+    }
+}
+
+impl fmt::Display for LogEntry {
+    /// `LEVEL message key=value ...`, as exported
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<5} {}", self.level, self.message)?;
+        for (key, value) in &self.fields {
+            write!(f, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
 
 /// Shared structure for storing log messages in a circular buffer
 pub struct LogBuffer {
-    /// Thread-safe deque containing log level and message pairs
-    pub logs: Mutex<VecDeque<(log::Level, String)>>,
+    /// Thread-safe deque containing the log entries, oldest first
+    pub logs: Mutex<VecDeque<LogEntry>>,
     /// Maximum number of log entries to keep
     pub max_size: usize,
 }
@@ -18,6 +210,15 @@ impl LogBuffer {
             max_size,
         }
     }
+
+    /// Appends an entry, removing the oldest one if at capacity
+    pub fn push(&self, entry: LogEntry) {
+        let mut logs = self.logs.lock().unwrap();
+        if logs.len() >= self.max_size {
+            logs.pop_front();
+        }
+        logs.push_back(entry);
+    }
 }
 
 /// Custom logger implementation that captures log messages into a shared buffer
@@ -36,17 +237,244 @@ impl Log for SimpleTuiLogger {
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            let mut logs = self.buffer.logs.lock().unwrap();
+            let message = format!("{}", record.args());
+            let mut fields = FieldCollector::default();
+            // A failing source keeps the fields visited before it
+            let _ = record.key_values().visit(&mut fields);
+            self.buffer.push(LogEntry::new(
+                record.level(),
+                record.target(),
+                message,
+                fields.0,
+            ));
+        }
+    }
 
-            // Remove oldest entry if at capacity
-            if logs.len() >= self.buffer.max_size {
-                logs.pop_front();
+    fn flush(&self) {}
+}
+
+/// `tracing` layer that feeds the same LogBuffer as SimpleTuiLogger
+///
+/// Dependencies like the orchestrator may emit `tracing` events instead of
+/// `log` records. Each event becomes a log entry whose message is prefixed
+/// with the spans it happened in, outermost first (`planet:handler: ...`),
+/// and whose fields include those of the spans as `<span>.<field>`, so an
+/// event inside `planet{id=3}` is about planet 3.
+pub struct TuiTracingLayer {
+    pub buffer: Arc<LogBuffer>,
+}
+
+/// Fields of a span, stored in its extensions until the span closes
+struct SpanFields(Vec<(String, FieldValue)>);
+
+/// Collects the fields of a span or event, keeping the `message` field apart
+#[derive(Default)]
+struct TracingFieldCollector {
+    message: Option<String>,
+    fields: Vec<(String, FieldValue)>,
+}
+
+impl TracingFieldCollector {
+    fn push(&mut self, field: &Field, value: FieldValue) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.fields.push((field.name().to_string(), value));
+        }
+    }
+}
+
+impl Visit for TracingFieldCollector {
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, FieldValue::Bool(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, FieldValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        let value = i64::try_from(value)
+            .map(FieldValue::Int)
+            .unwrap_or_else(|_| FieldValue::Str(value.to_string()));
+        self.push(field, value);
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.push(field, FieldValue::Float(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, FieldValue::Str(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.push(field, FieldValue::Str(format!("{:?}", value)));
+    }
+}
+
+impl<S> Layer<S> for TuiTracingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = TracingFieldCollector::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(SpanFields(fields.fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &SpanRecord<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut recorded = TracingFieldCollector::default();
+        values.record(&mut recorded);
+        let mut extensions = span.extensions_mut();
+        let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() else {
+            return;
+        };
+        for (key, value) in recorded.fields {
+            match fields.iter_mut().find(|(field, _)| *field == key) {
+                Some((_, old)) => *old = value,
+                None => fields.push((key, value)),
             }
+        }
+    }
 
-            let message = format!("{}", record.args());
-            logs.push_back((record.level(), message));
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut collector = TracingFieldCollector::default();
+        event.record(&mut collector);
+
+        // The event's own fields first, then the ones of its spans from the innermost
+        let mut fields = collector.fields;
+        let mut path = Vec::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope {
+                path.push(span.name());
+                if let Some(SpanFields(span_fields)) = span.extensions().get::<SpanFields>() {
+                    fields.extend(
+                        span_fields.iter().map(|(key, value)| {
+                            (format!("{}.{}", span.name(), key), value.clone())
+                        }),
+                    );
+                }
+            }
+        }
+        path.reverse();
+
+        let mut message = collector.message.unwrap_or_default();
+        if !path.is_empty() {
+            message = format!("{}: {}", path.join(":"), message);
         }
+        self.buffer.push(LogEntry::new(
+            log_level(*metadata.level()),
+            metadata.target(),
+            message,
+            fields,
+        ));
     }
+}
 
-    fn flush(&self) {}
+fn log_level(level: tracing::Level) -> log::Level {
+    if level == tracing::Level::ERROR {
+        log::Level::Error
+    } else if level == tracing::Level::WARN {
+        log::Level::Warn
+    } else if level == tracing::Level::INFO {
+        log::Level::Info
+    } else if level == tracing::Level::DEBUG {
+        log::Level::Debug
+    } else {
+        log::Level::Trace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_separator() {
+        for text in ["planet 3", "planet_3", "planet-3", "planet3", "planet: 3"] {
+            assert_eq!(Entity::parse(text), Some(Entity::Planet(3)), "{}", text);
+        }
+        assert_eq!(
+            Entity::parse("explorer 12 died"),
+            Some(Entity::Explorer(12))
+        );
+    }
+
+    #[test]
+    fn needs_a_kind_and_a_number() {
+        assert_eq!(Entity::parse("planet"), None);
+        assert_eq!(Entity::parse("planet x"), None);
+        assert_eq!(Entity::parse("moon 3"), None);
+        assert_eq!(Entity::parse("planet 99999999999"), None);
+    }
+
+    #[test]
+    fn finds_every_entity_once_in_order() {
+        assert_eq!(
+            Entity::find_all("Explorer 0 died on PLANET 2, explorer 0 was alone"),
+            [Entity::Explorer(0), Entity::Planet(2)]
+        );
+        assert_eq!(
+            Entity::find_all("omc_galaxy::planet::3"),
+            [Entity::Planet(3)]
+        );
+    }
+
+    #[test]
+    fn finds_entities_only_at_word_starts() {
+        assert!(Entity::find_all("subplanet 3 and planets").is_empty());
+        assert_eq!(Entity::find_all("(planet_4)"), [Entity::Planet(4)]);
+    }
+
+    #[test]
+    fn keeps_offsets_with_non_ascii_text() {
+        assert_eq!(
+            Entity::find_all("Énergie du planet 5 épuisée"),
+            [Entity::Planet(5)]
+        );
+    }
+
+    #[test]
+    fn entries_take_entities_from_target_fields_and_message() {
+        let entry = LogEntry::new(
+            log::Level::Info,
+            "omc_galaxy::explorer::1",
+            "moved to planet 2".to_string(),
+            vec![("source_planet".to_string(), FieldValue::Int(7))],
+        );
+        assert_eq!(
+            entry.entities,
+            [Entity::Explorer(1), Entity::Planet(7), Entity::Planet(2)]
+        );
+        assert!(entry.concerns(Entity::Planet(7)));
+        assert!(!entry.concerns(Entity::Explorer(2)));
+    }
+
+    #[test]
+    fn entries_take_entities_only_from_keys_naming_one() {
+        let field = |key: &str, value| (key.to_string(), FieldValue::Int(value));
+        let entry = LogEntry::new(
+            log::Level::Info,
+            "ratatui_gui",
+            "tick".to_string(),
+            vec![
+                field("planet_id", 1),
+                field("target_explorer_id", 4),
+                field("planets_alive", 3),
+                field("explorers_count", 2),
+                field("exoplanet", 5),
+            ],
+        );
+        assert_eq!(entry.entities, [Entity::Planet(1), Entity::Explorer(4)]);
+        assert!(!entry.concerns(Entity::Planet(3)));
+        assert!(!entry.concerns(Entity::Explorer(2)));
+    }
 }
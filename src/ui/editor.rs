@@ -0,0 +1,315 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::editor::GalaxyEditor;
+use crate::theme::Theme;
+
+/// Width of a single cell of the adjacency grid
+const GRID_CELL_WIDTH: u16 = 4;
+
+/// Renders the galaxy file editor: planet list, adjacency grid and problems
+pub fn render_editor_screen(editor: &GalaxyEditor, theme: &Theme, frame: &mut Frame) {
+    let outer_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // File info
+            Constraint::Fill(1),   // Planets + grid
+            Constraint::Length(8), // Problems
+            Constraint::Length(1), // Key hints
+        ])
+        .split(frame.area());
+
+    let main_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(26), Constraint::Fill(1)])
+        .split(outer_layout[1]);
+
+    render_file_info(editor, theme, frame, outer_layout[0]);
+    render_planet_list(editor, theme, frame, main_layout[0]);
+    render_adjacency_grid(editor, theme, frame, main_layout[1]);
+    render_problems(editor, theme, frame, outer_layout[2]);
+
+    let hints = Line::from(vec![
+        Span::styled(
+            " ←↑↓→ ",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("move  ", Style::default().fg(theme.label)),
+        Span::styled(
+            "SPACE ",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("link  ", Style::default().fg(theme.label)),
+        Span::styled(
+            "a ",
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("add  ", Style::default().fg(theme.label)),
+        Span::styled(
+            "d ",
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("remove  ", Style::default().fg(theme.label)),
+        Span::styled(
+            "t ",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("type  ", Style::default().fg(theme.label)),
+        Span::styled(
+            "s ",
+            Style::default()
+                .fg(theme.special)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("save  ", Style::default().fg(theme.label)),
+        Span::styled(
+            "r ",
+            Style::default()
+                .fg(theme.special)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("reload  ", Style::default().fg(theme.label)),
+        Span::styled(
+            "ESC ",
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("back", Style::default().fg(theme.label)),
+    ]);
+    frame.render_widget(Paragraph::new(hints), outer_layout[3]);
+}
+
+fn render_file_info(editor: &GalaxyEditor, theme: &Theme, frame: &mut Frame, area: Rect) {
+    let text = Line::from(vec![
+        Span::styled("File: ", Style::default().fg(theme.label)),
+        Span::styled(
+            editor.path.clone(),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            if editor.dirty { " [modified]" } else { "" },
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" | ", Style::default().fg(theme.label)),
+        Span::styled("Planets: ", Style::default().fg(theme.label)),
+        Span::styled(
+            editor.galaxy.planets.len().to_string(),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" | ", Style::default().fg(theme.label)),
+        Span::styled("Links: ", Style::default().fg(theme.label)),
+        Span::styled(
+            editor.galaxy.link_count().to_string(),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" | ", Style::default().fg(theme.label)),
+        Span::styled(editor.status.clone(), Style::default().fg(theme.accent)),
+    ]);
+
+    let paragraph = Paragraph::new(text).block(
+        Block::bordered()
+            .title(" Galaxy Editor ")
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_planet_list(editor: &GalaxyEditor, theme: &Theme, frame: &mut Frame, area: Rect) {
+    let header = Row::new(vec!["ID", "Type", "Links"]).style(
+        Style::default()
+            .fg(theme.header)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let rows: Vec<Row> = editor
+        .galaxy
+        .planets
+        .iter()
+        .map(|planet| {
+            Row::new(vec![
+                Cell::from(planet.id.to_string()),
+                Cell::from(planet.planet_type.to_string()),
+                Cell::from(planet.neighbours.len().to_string()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Min(5),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::bordered()
+            .title(" Planets ")
+            .border_style(Style::default().fg(theme.border)),
+    )
+    .row_highlight_style(
+        Style::default()
+            .bg(theme.highlight_bg)
+            .fg(theme.highlight_fg),
+    );
+
+    let mut state =
+        TableState::default().with_selected(editor.selected_planet().map(|_| editor.cursor_row));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+/// First index to show so that `cursor` stays inside a window of `visible` items
+fn scroll_offset(cursor: usize, visible: usize) -> usize {
+    if visible == 0 || cursor < visible {
+        0
+    } else {
+        cursor + 1 - visible
+    }
+}
+
+fn render_adjacency_grid(editor: &GalaxyEditor, theme: &Theme, frame: &mut Frame, area: Rect) {
+    let planets = &editor.galaxy.planets;
+
+    // Space left once borders, the header row and the label column are removed
+    let visible_rows = area.height.saturating_sub(3) as usize;
+    let visible_cols = (area.width.saturating_sub(2 + GRID_CELL_WIDTH) / GRID_CELL_WIDTH) as usize;
+    let first_row = scroll_offset(editor.cursor_row, visible_rows);
+    let first_col = scroll_offset(editor.cursor_col, visible_cols);
+
+    let width = GRID_CELL_WIDTH as usize;
+    let label_style = Style::default()
+        .fg(theme.warning)
+        .add_modifier(Modifier::BOLD);
+
+    let mut header = vec![Span::raw(" ".repeat(width))];
+    header.extend(
+        planets
+            .iter()
+            .enumerate()
+            .skip(first_col)
+            .take(visible_cols)
+            .map(|(col, p)| {
+                let style = if col == editor.cursor_col {
+                    label_style.add_modifier(Modifier::REVERSED)
+                } else {
+                    label_style
+                };
+                Span::styled(format!("{:^width$}", p.id), style)
+            }),
+    );
+
+    let mut lines = vec![Line::from(header)];
+    for (row, planet) in planets
+        .iter()
+        .enumerate()
+        .skip(first_row)
+        .take(visible_rows)
+    {
+        let style = if row == editor.cursor_row {
+            label_style.add_modifier(Modifier::REVERSED)
+        } else {
+            label_style
+        };
+        let mut spans = vec![Span::styled(format!("{:>3} ", planet.id), style)];
+
+        for (col, other) in planets
+            .iter()
+            .enumerate()
+            .skip(first_col)
+            .take(visible_cols)
+        {
+            let linked = planet.neighbours.contains(&other.id);
+            // A one-way link is shown in red so it can be fixed from the grid
+            let (symbol, color) = if planet.id == other.id {
+                ("╲", theme.muted)
+            } else if linked && !editor.galaxy.is_linked(other.id, planet.id) {
+                ("●", theme.error)
+            } else if linked {
+                ("●", theme.success)
+            } else {
+                ("·", theme.muted)
+            };
+
+            let mut cell_style = Style::default().fg(color);
+            if row == editor.cursor_row && col == editor.cursor_col {
+                cell_style = cell_style.bg(theme.muted).add_modifier(Modifier::BOLD);
+            }
+            spans.push(Span::styled(format!("{:^width$}", symbol), cell_style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(" Adjacency ")
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_problems(editor: &GalaxyEditor, theme: &Theme, frame: &mut Frame, area: Rect) {
+    let mut lines: Vec<Line> = editor
+        .parse_errors
+        .iter()
+        .map(|error| {
+            Line::from(vec![
+                Span::styled(
+                    "PARSE ",
+                    Style::default()
+                        .fg(theme.error)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(error.to_string(), Style::default().fg(theme.text)),
+            ])
+        })
+        .collect();
+
+    lines.extend(editor.issues.iter().map(|issue| {
+        Line::from(vec![
+            Span::styled(
+                "CHECK ",
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(issue.clone(), Style::default().fg(theme.text)),
+        ])
+    }));
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No problems found",
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .title(" Problems ")
+                .border_style(Style::default().fg(theme.border)),
+        )
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,282 @@
+use omc_galaxy::Status;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, BorderType, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table},
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::theme::Theme;
+
+/// Renders the end-of-game summary
+///
+/// Shows how long the galaxy survived, planet deaths, surviving explorers,
+/// event and energy totals and a chart of alive planets over time.
+pub fn render_end_screen(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let outer_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Banner
+            Constraint::Fill(1),   // Summary
+            Constraint::Length(1), // Key hints
+        ])
+        .split(frame.area());
+
+    let main_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(outer_layout[1]);
+
+    let left_column = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(app.explorers_info.len() as u16 + 3),
+        ])
+        .split(main_layout[0]);
+
+    let right_column = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Fill(1)])
+        .split(main_layout[1]);
+
+    render_banner(app, frame, outer_layout[0]);
+    render_planet_deaths(app, frame, left_column[0]);
+    render_explorers_outcome(app, frame, left_column[1]);
+    render_totals(app, frame, right_column[0]);
+    render_alive_chart(app, frame, right_column[1]);
+
+    let mut hints = vec![
+        Span::styled(
+            format!(" {} ", app.keymap.label(Action::Restart)),
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("restart  ", Style::default().fg(theme.label)),
+        Span::styled(
+            format!("{} ", app.keymap.label(Action::ExportReport)),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("export report  ", Style::default().fg(theme.label)),
+        Span::styled(
+            format!("{} ", app.keymap.label(Action::Quit)),
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("quit  ", Style::default().fg(theme.label)),
+    ];
+    if let Some(status) = &app.end_status {
+        hints.push(Span::styled(
+            status.clone(),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(hints)), outer_layout[2]);
+}
+
+fn render_banner(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let duration = app.stats.duration().as_secs();
+    let text = Line::from(vec![
+        Span::styled(
+            " GAME OVER ",
+            Style::default()
+                .fg(theme.text)
+                .bg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" | ", Style::default().fg(theme.label)),
+        Span::styled("Galaxy survived ", Style::default().fg(theme.label)),
+        Span::styled(
+            format!(
+                "{} ticks ({}m {:02}s)",
+                app.stats.tick,
+                duration / 60,
+                duration % 60
+            ),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+    ]);
+
+    let banner = Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .border_type(BorderType::Thick)
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(banner, area);
+}
+
+pub(crate) fn render_planet_deaths(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let header = Row::new(vec!["#", "Planet", "Tick"]).style(
+        Style::default()
+            .fg(theme.header)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let rows: Vec<Row> = app
+        .stats
+        .planet_deaths
+        .iter()
+        .enumerate()
+        .map(|(order, (id, tick))| {
+            Row::new(vec![
+                Cell::from((order + 1).to_string()),
+                Cell::from(id.to_string()),
+                Cell::from(tick.to_string()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Min(7),
+            Constraint::Min(7),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::bordered()
+            .title(" Planet Deaths ")
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(table, area);
+}
+
+fn render_explorers_outcome(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let rows: Vec<Row> = app
+        .explorers_info
+        .iter()
+        .map(|(id, info)| {
+            let (outcome, color) = match info.status {
+                Status::Dead => ("Dead", theme.dead),
+                _ => ("Survived", theme.alive),
+            };
+            Row::new(vec![
+                Cell::from(format!("Explorer {}", id)),
+                Cell::from(outcome).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Min(12), Constraint::Min(9)]).block(
+        Block::bordered()
+            .title(" Explorers ")
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(table, area);
+}
+
+pub(crate) fn render_totals(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let stats = &app.stats;
+    let value_style = Style::default().fg(theme.text).add_modifier(Modifier::BOLD);
+
+    let text = vec![
+        Line::from(vec![
+            Span::styled("  Random events: ", Style::default().fg(theme.label)),
+            Span::styled(stats.random_events.to_string(), value_style),
+            Span::styled(
+                " (kind picked by the orchestrator)",
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Peak energy: ", Style::default().fg(theme.label)),
+            Span::styled(format!("{} cells", stats.peak_energy), value_style),
+            Span::styled("  Final energy: ", Style::default().fg(theme.label)),
+            Span::styled(format!("{} cells", stats.final_energy), value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("  Planets dead: ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!(
+                    "{} of {}",
+                    stats.planet_deaths.len(),
+                    app.planets_info.len()
+                ),
+                value_style,
+            ),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::bordered()
+            .title(" Totals ")
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_alive_chart(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    render_history_chart(
+        frame,
+        area,
+        " Alive Planets Over Time ",
+        "alive planets",
+        &app.stats.alive_history,
+        theme.alive,
+        &theme,
+    );
+}
+
+/// Line chart of a value recorded every tick, from 0 to its highest value
+pub(crate) fn render_history_chart(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    name: &str,
+    history: &[usize],
+    color: Color,
+    theme: &Theme,
+) {
+    let data: Vec<(f64, f64)> = history
+        .iter()
+        .enumerate()
+        .map(|(tick, value)| (tick as f64, *value as f64))
+        .collect();
+
+    let max_tick = (history.len().saturating_sub(1)).max(1) as f64;
+    let max_value = history.iter().copied().max().unwrap_or_default().max(1) as f64;
+
+    let dataset = Dataset::default()
+        .name(name.to_string())
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(&data);
+
+    let chart = Chart::new(vec![dataset])
+        .block(
+            Block::bordered()
+                .title(title.to_string())
+                .border_style(Style::default().fg(theme.border)),
+        )
+        .x_axis(
+            Axis::default()
+                .title("tick")
+                .style(Style::default().fg(theme.label))
+                .bounds([0.0, max_tick])
+                .labels(["0".to_string(), format!("{}", max_tick as u64)]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.label))
+                .bounds([0.0, max_value])
+                .labels(["0".to_string(), format!("{}", max_value as u64)]),
+        );
+    frame.render_widget(chart, area);
+}
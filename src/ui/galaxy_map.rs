@@ -0,0 +1,117 @@
+use std::f64::consts::TAU;
+
+use ratatui::{
+    Frame,
+    layout::{Margin, Rect},
+    style::{Modifier, Style},
+    symbols::Marker,
+    text::Span,
+    widgets::{
+        Block,
+        canvas::{Canvas, Line},
+    },
+};
+
+use crate::theme::Theme;
+
+/// Places the planets evenly on a circle, in canvas coordinates ([-1, 1] on both axes)
+pub(crate) fn node_positions(ids: &[u32]) -> Vec<(u32, f64, f64)> {
+    let n = ids.len().max(1) as f64;
+    ids.iter()
+        .enumerate()
+        .map(|(i, id)| {
+            let angle = TAU * i as f64 / n;
+            (*id, 0.85 * angle.cos(), 0.85 * angle.sin())
+        })
+        .collect()
+}
+
+/// Planet whose label is at the given terminal cell of a map drawn in `area`
+///
+/// Mirrors where the canvas prints the labels, with one cell of tolerance
+/// around them since the labels are small targets.
+pub(crate) fn node_at(area: Rect, ids: &[u32], column: u16, row: u16) -> Option<u32> {
+    let inner = area.inner(Margin::new(1, 1));
+    if inner.is_empty() {
+        return None;
+    }
+
+    node_positions(ids)
+        .into_iter()
+        .filter_map(|(id, x, y)| {
+            let label_x = inner.x + ((x + 1.0) / 2.0 * (inner.width - 1) as f64) as u16;
+            let label_y = inner.y + ((1.0 - y) / 2.0 * (inner.height - 1) as f64) as u16;
+            let label_end = label_x + id.to_string().len() as u16;
+            let hit = row.abs_diff(label_y) <= 1 && column + 1 >= label_x && column <= label_end;
+            hit.then(|| (row.abs_diff(label_y) + column.abs_diff(label_x), id))
+        })
+        .min()
+        .map(|(_, id)| id)
+}
+
+/// Renders the galaxy topology as a graph: planets on a circle, links as lines
+///
+/// The `selected` planet and its links are highlighted.
+pub(crate) fn render_galaxy_map(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    ids: &[u32],
+    links: &[(u32, u32)],
+    selected: Option<u32>,
+    theme: &Theme,
+) {
+    let positions = node_positions(ids);
+    let position = |id: u32| {
+        positions
+            .iter()
+            .find(|(node, _, _)| *node == id)
+            .map(|(_, x, y)| (*x, *y))
+    };
+
+    let canvas = Canvas::default()
+        .block(
+            Block::bordered()
+                .title(format!(" {} ", title))
+                .border_style(Style::default().fg(theme.border)),
+        )
+        .marker(Marker::Braille)
+        .x_bounds([-1.0, 1.0])
+        .y_bounds([-1.0, 1.0])
+        .paint(|ctx| {
+            for (a, b) in links {
+                if let (Some((x1, y1)), Some((x2, y2))) = (position(*a), position(*b)) {
+                    let color = if selected == Some(*a) || selected == Some(*b) {
+                        theme.success
+                    } else {
+                        theme.muted
+                    };
+                    ctx.draw(&Line {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        color,
+                    });
+                }
+            }
+
+            // Labels go on a new layer so they are drawn over the links
+            ctx.layer();
+            for (id, x, y) in &positions {
+                let style = if selected == Some(*id) {
+                    Style::default()
+                        .fg(theme.selected_fg)
+                        .bg(theme.success)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD)
+                };
+                ctx.print(*x, *y, Span::styled(id.to_string(), style));
+            }
+        });
+
+    frame.render_widget(canvas, area);
+}
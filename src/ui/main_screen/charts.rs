@@ -0,0 +1,46 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Sparkline},
+};
+
+use crate::app::App;
+
+/// Renders alive planets and total energy over the last ticks, as much as fits
+pub(crate) fn render_history_charts(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let charts = [
+        (" Alive Planets ", &app.stats.alive_history, theme.alive),
+        (
+            " Total Energy ",
+            &app.stats.energy_history,
+            theme.energy_charged,
+        ),
+    ];
+    for ((title, history, color), area) in charts.into_iter().zip(layout.iter()) {
+        // Latest values on the right
+        let visible = area.width.saturating_sub(2) as usize;
+        let data: Vec<u64> = history
+            .iter()
+            .skip(history.len().saturating_sub(visible))
+            .map(|value| *value as u64)
+            .collect();
+        let last = history.last().copied().unwrap_or_default();
+
+        let sparkline = Sparkline::default()
+            .block(
+                Block::bordered()
+                    .title(format!("{}{} ", title, last))
+                    .border_style(Style::default().fg(theme.border)),
+            )
+            .data(&data)
+            .style(Style::default().fg(color));
+        frame.render_widget(sparkline, *area);
+    }
+}
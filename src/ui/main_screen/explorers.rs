@@ -1,17 +1,20 @@
-use omc_galaxy::Status;
 use ratatui::{
     Frame,
     layout::{Constraint, Rect},
-    style::{Color, Modifier, Style},
-    widgets::{Block, Cell, Row, Table},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Table, Wrap},
 };
 
 use crate::app::App;
+use crate::tui_loggers::Entity;
+use crate::ui::{highlight_style, highlight_symbol, status_text};
 
-pub fn render_explorers(app: &App, frame: &mut Frame, area: Rect) {
+pub fn render_explorers(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
     let header = Row::new(vec!["ID", "Status", "Bag", "Planet"]).style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.header)
             .add_modifier(Modifier::BOLD),
     );
 
@@ -19,17 +22,14 @@ pub fn render_explorers(app: &App, frame: &mut Frame, area: Rect) {
         .explorers_info
         .iter()
         .map(|(id, info)| {
-            let status = match info.status {
-                Status::Running => "Running",
-                Status::Paused => "Paused",
-                Status::Dead => "Dead",
-            };
+            let status = status_text(&info.status);
             let bag = "[ ]".repeat(5);
             let planet_id = info.current_planet_id.to_string();
 
             Row::new(vec![
                 Cell::from(id.to_string()),
-                Cell::from(status.to_string()),
+                Cell::from(status.to_string())
+                    .style(Style::default().fg(theme.status(&info.status))),
                 Cell::from(bag),
                 Cell::from(planet_id),
             ])
@@ -64,8 +64,94 @@ pub fn render_explorers(app: &App, frame: &mut Frame, area: Rect) {
     .block(
         Block::bordered()
             .title(" Explorers ")
-            .border_style(Style::default().fg(Color::LightRed)),
-    );
+            .border_style(Style::default().fg(theme.border)),
+    )
+    .row_highlight_style(highlight_style(&theme, app.accessible))
+    .highlight_symbol(highlight_symbol(app.accessible));
+
+    frame.render_stateful_widget(table, area, &mut app.explorer_table_state);
+    app.click_areas.explorers_table = area;
+}
+
+/// Renders the explorer selected in the table and where it can go from its planet
+pub(crate) fn render_explorer_details(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let selected = app
+        .explorer_table_state
+        .selected()
+        .and_then(|i| app.explorers_info.iter().nth(i));
 
-    frame.render_widget(table, area);
+    let mut text = vec![Line::from("")];
+    match selected {
+        Some((id, info)) => {
+            let planet = info.current_planet_id;
+            let status = status_text(&info.status);
+            let neighbours: Vec<String> = app
+                .galaxy_topology
+                .get(planet as usize)
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, linked)| **linked)
+                        .map(|(neighbour, _)| neighbour.to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let neighbours = if neighbours.is_empty() {
+                "None".to_string()
+            } else {
+                neighbours.join(", ")
+            };
+
+            for (label, value) in [
+                ("Explorer ID", id.to_string()),
+                ("Status", status.to_string()),
+                ("Planet", planet.to_string()),
+                ("Can reach", neighbours),
+            ] {
+                text.push(Line::from(vec![
+                    Span::styled(format!("  {}: ", label), Style::default().fg(theme.label)),
+                    Span::styled(
+                        value,
+                        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                    ),
+                ]));
+                text.push(Line::from(""));
+            }
+
+            text.push(Line::from(Span::styled(
+                "  Recent Log",
+                Style::default()
+                    .fg(theme.header)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            let lines = app.entity_log_lines(Entity::Explorer(*id), 5);
+            if lines.is_empty() {
+                text.push(Line::from(Span::styled(
+                    "  Nothing yet",
+                    Style::default().fg(theme.muted),
+                )));
+            }
+            for (level, message) in lines {
+                text.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {:<5} ", level),
+                        Style::default().fg(theme.log_level(level)),
+                    ),
+                    Span::styled(message, Style::default().fg(theme.text)),
+                ]));
+            }
+        }
+        None => text.push(Line::from(Span::styled(
+            "  Click an explorer to select it",
+            Style::default().fg(theme.muted),
+        ))),
+    }
+
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::bordered()
+            .title(" Explorer ")
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(paragraph, area);
 }
@@ -1,70 +1,79 @@
 use ratatui::{
     Frame,
-    layout::{Alignment, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::{app::App, game_state::GameState};
+use crate::{app::App, game_state::GameState, ui::overlays};
 
-pub fn render_globals_info(app: &App, frame: &mut Frame, area: Rect) {
+pub fn render_globals_info(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
     let title_text = vec![Line::from(vec![
-        Span::styled("Game: ", Style::default().fg(Color::Gray)),
+        Span::styled("Game: ", Style::default().fg(theme.label)),
         Span::styled(
             format!("{:?}", app.gamestate),
             match app.gamestate {
-                GameState::Running => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                GameState::Running => Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
                 GameState::Paused => Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
                 GameState::Ended => Style::default()
-                    .fg(Color::Blue)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
-                GameState::WaitingStart => Style::default()
-                    .fg(Color::LightCyan)
+                GameState::WaitingStart | GameState::Editing => Style::default()
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             },
         ),
-        Span::styled(" | ", Style::default().fg(Color::Gray)),
-        Span::styled("Simulation Time: ", Style::default().fg(Color::Gray)),
+        Span::styled(" | ", Style::default().fg(theme.label)),
+        Span::styled("Simulation Time: ", Style::default().fg(theme.label)),
         Span::styled(
-            format!("???",),
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            format!("{} ticks", app.stats.tick),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
-        Span::styled(" | ", Style::default().fg(Color::Gray)),
-        Span::styled("Total Planets: ", Style::default().fg(Color::Gray)),
+        Span::styled(" | ", Style::default().fg(theme.label)),
+        Span::styled("Total Planets: ", Style::default().fg(theme.label)),
         Span::styled(
             format!("{}", app.planets_info.len()),
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
-        Span::styled(" | ", Style::default().fg(Color::Gray)),
-        Span::styled("Total Explorers: ", Style::default().fg(Color::Gray)),
+        Span::styled(" | ", Style::default().fg(theme.label)),
+        Span::styled("Total Explorers: ", Style::default().fg(theme.label)),
         Span::styled(
             format!("{}", app.explorers_info.len()),
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(" | ", Style::default().fg(Color::Gray)),
-        Span::styled("Sunray%: ", Style::default().fg(Color::Gray)),
-        Span::styled(
-            format!("{}%", app.probability_sunray),
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
     ])];
 
+    // Game info on the left, sunray gauge on the right
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Length(24)])
+        .split(area);
+    let area = layout[0];
+
+    // `Game: <state>` is clickable, the line starts right after the border
+    let width: usize = title_text[0].spans[..2].iter().map(Span::width).sum();
+    app.click_areas.game_state =
+        Rect::new(area.x + 1, area.y + 1, width as u16, 1).intersection(area);
+
     let title = Paragraph::new(title_text).alignment(Alignment::Left).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Blue))
+            .border_style(Style::default().fg(theme.border))
             .style(Style::default()),
     );
     frame.render_widget(title, area);
+
+    let gauge = overlays::sunray_gauge(app.probability_sunray, &theme).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(gauge, layout[1]);
+    app.click_areas.sunray = layout[1];
 }
@@ -1,86 +1,77 @@
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Paragraph},
 };
 
 use crate::app::App;
+use crate::keymap::{Action, ActionGroup};
+use crate::ui::action_color;
 
+/// Bound actions available now, without the tab keys already shown in the tab bar
+fn shown_actions(app: &App) -> Vec<Action> {
+    app.keymap
+        .available(&app.get_game_state())
+        .into_iter()
+        .filter(|action| action.group() != ActionGroup::Tabs)
+        .collect()
+}
+
+/// Renders the bindings available in the current state, as read from the keymap
 pub(crate) fn render_instructions(app: &App, frame: &mut Frame, area: Rect) {
-    let text = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "  Q ",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("- Quit", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  P ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("- Pause/Resume", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  W ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                "- Select Previous Planet",
-                Style::default().fg(Color::White),
-            ),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  S ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("- Select Next Planet", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  L ",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("- Toggle Log Overlay", Style::default().fg(Color::White)),
-        ]),
+    let theme = app.theme();
+    let actions = shown_actions(app);
+    let width = actions
+        .iter()
+        .map(|action| app.keymap.label(*action).chars().count())
+        .max()
+        .unwrap_or(1);
+
+    let mut text = vec![Line::from("")];
+    text.extend(actions.into_iter().map(|action| {
         Line::from(vec![
             Span::styled(
-                "  ↑ ",
+                format!("  {:<width$} ", app.keymap.label(action)),
                 Style::default()
-                    .fg(Color::Magenta)
+                    .fg(action_color(action, &theme))
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("- Increase Sunray %", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
             Span::styled(
-                "  ↓ ",
-                Style::default()
-                    .fg(Color::Magenta)
-                    .add_modifier(Modifier::BOLD),
+                format!("- {}", action.description()),
+                Style::default().fg(theme.text),
             ),
-            Span::styled("- Decrease Sunray %", Style::default().fg(Color::White)),
-        ]),
-    ];
+        ])
+    }));
 
     let paragraph = Paragraph::new(text).block(
         Block::bordered()
             .title(" Instructions ")
-            .border_style(Style::default().fg(Color::Yellow)),
+            .border_style(Style::default().fg(theme.border)),
     );
     frame.render_widget(paragraph, area);
 }
+
+/// One-line version of the instructions, used when the panel is collapsed
+pub(crate) fn render_hint_bar(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let spans: Vec<Span> = shown_actions(app)
+        .into_iter()
+        .flat_map(|action| {
+            [
+                Span::styled(
+                    format!(" {} ", app.keymap.label(action)),
+                    Style::default()
+                        .fg(action_color(action, &theme))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{} ", action.description()),
+                    Style::default().fg(theme.label),
+                ),
+            ]
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
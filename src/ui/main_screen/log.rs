@@ -1,61 +1,223 @@
 use crate::app::App;
-use log::Level;
+use crate::keymap::Action;
+use crate::tui_loggers::LogEntry;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Paragraph, Wrap},
+    widgets::{Block, Paragraph},
 };
 
-/// Render overlay dei log che copre solo la colonna destra
-pub fn render_log_overlay(app: &App, frame: &mut Frame, area: Rect) {
-    let logs_lock = app.log_entries.logs.lock().unwrap();
+/// Fields shown in aligned columns, the others follow them as a `key=value` list
+const MAX_FIELD_COLUMNS: usize = 4;
+/// Width of the level in front of each message
+const LEVEL_WIDTH: usize = 6;
 
-    let mut lines: Vec<Line> = logs_lock
-        .iter()
-        .map(|(level, msg)| {
-            let color = match *level {
-                Level::Error => Color::Red,
-                Level::Warn => Color::Yellow,
-                Level::Info => Color::Green,
-                Level::Debug => Color::Cyan,
-                Level::Trace => Color::DarkGray,
-            };
-
-            Line::from(vec![
-                Span::styled(
-                    format!("{:<5} ", level),
-                    Style::default().fg(color).add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(msg.clone(), Style::default().fg(Color::White)),
-            ])
-        })
-        .collect();
+/// Render overlay dei log che copre solo la colonna destra
+pub fn render_log_overlay(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    // Two rows under the logs for the instructions
+    let mut lines = log_lines(
+        app,
+        area.width.saturating_sub(2) as usize,
+        area.height.saturating_sub(4) as usize,
+    );
 
     // Aggiungi istruzioni in fondo
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("Press ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Press ", Style::default().fg(theme.muted)),
         Span::styled(
-            "L",
+            app.keymap.label(Action::ToggleLog),
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.success)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(" to close", Style::default().fg(Color::DarkGray)),
+        Span::styled(" to close", Style::default().fg(theme.muted)),
     ]));
 
     let log_overlay = Paragraph::new(lines)
         .block(
-            Block::bordered().title(" Game Logs").border_style(
+            Block::bordered().title(log_title(app)).border_style(
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
             ),
         )
-        .style(Style::default()) // Background nero per contrasto
-        .wrap(Wrap { trim: true });
+        .style(Style::default()); // Background nero per contrasto
 
     frame.render_widget(log_overlay, area);
+    app.click_areas.log_overlay = area;
+}
+
+/// Renders the logs as a full-size tab, scrolled like the overlay
+pub(crate) fn render_log_tab(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let lines = log_lines(
+        app,
+        area.width.saturating_sub(2) as usize,
+        area.height.saturating_sub(2) as usize,
+    );
+    let logs = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(log_title(app))
+            .border_style(Style::default().fg(theme.border)),
+    );
+
+    frame.render_widget(logs, area);
+    app.click_areas.log_overlay = area;
+}
+
+/// The `rows` lines ending `app.log_scroll` lines before the latest one, coloured
+/// by level, only about `app.log_filter` if set
+///
+/// Only the entries that can reach the window are laid out, the buffer keeps
+/// thousands. The structured fields follow the message as `key=value` columns, each of the
+/// most used keys in its own column so the same field lines up across entries,
+/// then the rest of the fields. Rows with fields are cut at the border to keep
+/// the columns aligned, messages without fields wrap to `width`.
+fn log_lines(app: &App, width: usize, rows: usize) -> Vec<Line<'static>> {
+    let theme = app.theme();
+    let logs = app.log_entries.logs.lock().unwrap();
+    let scroll = app.log_scroll as usize;
+    // Every entry takes at least a line, so older ones can't reach the window
+    let mut entries: Vec<&LogEntry> = logs
+        .iter()
+        .rev()
+        .filter(|entry| app.log_filter.is_none_or(|entity| entry.concerns(entity)))
+        .take(rows + scroll)
+        .collect();
+    entries.reverse();
+    let message_width = entries
+        .iter()
+        .filter(|entry| !entry.fields.is_empty())
+        .map(|entry| entry.message.chars().count())
+        .max()
+        .unwrap_or(0);
+    let columns = field_columns(&entries);
+
+    let mut lines = Vec::new();
+    for entry in entries {
+        let color = theme.log_level(entry.level);
+        let level = Span::styled(
+            format!("{:<5} ", entry.level),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        );
+        if entry.fields.is_empty() {
+            let message_width = width.saturating_sub(LEVEL_WIDTH);
+            for (i, part) in wrap_words(&entry.message, message_width)
+                .into_iter()
+                .enumerate()
+            {
+                let prefix = if i == 0 {
+                    level.clone()
+                } else {
+                    Span::raw(" ".repeat(LEVEL_WIDTH))
+                };
+                lines.push(Line::from(vec![
+                    prefix,
+                    Span::styled(part, Style::default().fg(theme.text)),
+                ]));
+            }
+            continue;
+        }
+
+        let mut spans = vec![
+            level,
+            Span::styled(entry.message.clone(), Style::default().fg(theme.text)),
+            Span::raw(" ".repeat(message_width - entry.message.chars().count())),
+        ];
+        for (key, width) in &columns {
+            match entry.field(key) {
+                Some(value) => {
+                    let value = format!("{:<width$}", value.to_string(), width = *width);
+                    spans.push(Span::styled(
+                        format!("  {}=", key),
+                        Style::default().fg(theme.muted),
+                    ));
+                    spans.push(Span::styled(value, Style::default().fg(theme.accent)));
+                }
+                None => spans.push(Span::raw(" ".repeat(key.chars().count() + 3 + width))),
+            }
+        }
+        for (key, value) in &entry.fields {
+            if columns.iter().any(|(column, _)| column == key) {
+                continue;
+            }
+            spans.push(Span::styled(
+                format!("  {}=", key),
+                Style::default().fg(theme.muted),
+            ));
+            spans.push(Span::styled(
+                value.to_string(),
+                Style::default().fg(theme.accent),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    let end = lines.len().saturating_sub(scroll);
+    lines.drain(end..);
+    lines.drain(..end.saturating_sub(rows));
+    lines
+}
+
+/// The `MAX_FIELD_COLUMNS` keys logged most often, in the order they first appear,
+/// with the width of their widest value
+fn field_columns(entries: &[&LogEntry]) -> Vec<(String, usize)> {
+    // Key, entries with it and widest value
+    let mut keys: Vec<(String, usize, usize)> = Vec::new();
+    for (key, value) in entries.iter().flat_map(|entry| &entry.fields) {
+        let width = value.to_string().chars().count();
+        match keys.iter_mut().find(|(column, _, _)| column == key) {
+            Some((_, count, widest)) => {
+                *count += 1;
+                *widest = (*widest).max(width);
+            }
+            None => keys.push((key.clone(), 1, width)),
+        }
+    }
+
+    let mut most_used: Vec<usize> = (0..keys.len()).collect();
+    // Stable, so equally used keys keep their order
+    most_used.sort_by_key(|&i| std::cmp::Reverse(keys[i].1));
+    most_used.truncate(MAX_FIELD_COLUMNS);
+    most_used.sort_unstable();
+    most_used
+        .into_iter()
+        .map(|i| (keys[i].0.clone(), keys[i].2))
+        .collect()
+}
+
+/// Splits `text` at spaces into parts of at most `width` characters, cutting longer words
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        let used = part.chars().count();
+        if used > 0 && used + 1 + word.len() > width {
+            parts.push(std::mem::take(&mut part));
+        }
+        while word.len() > width {
+            parts.push(word.drain(..width).collect());
+        }
+        if !part.is_empty() {
+            part.push(' ');
+        }
+        part.extend(word);
+    }
+    if !part.is_empty() || parts.is_empty() {
+        parts.push(part);
+    }
+    parts
+}
+
+/// Title of the log views, naming the entity they are filtered to
+fn log_title(app: &App) -> String {
+    match app.log_filter {
+        Some(entity) => format!(" Game Logs - {} ", entity),
+        None => " Game Logs ".to_string(),
+    }
 }
@@ -1,111 +1,284 @@
+mod charts;
 mod explorers;
 mod global;
 mod instructions;
 mod log;
 mod planets;
+mod scenario;
+mod tabs;
+
+pub(crate) use tabs::Tab;
 
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Paragraph},
+    widgets::{Block, Paragraph, Wrap},
 };
 
 use crate::app::App;
+use crate::tui_loggers::Entity;
+use crate::ui::galaxy_map;
+
+/// Below this width the game UI uses a single column
+const COMPACT_WIDTH: u16 = 100;
+/// From this size on the galaxy map and the charts get their own column
+const WIDE_WIDTH: u16 = 160;
+const WIDE_HEIGHT: u16 = 36;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum LayoutMode {
+    /// One column: explorers, planets and extra info, instructions in the hint bar
+    Compact,
+    /// Explorers and planets on the left, extra info, map and instructions on the right
+    Normal,
+    /// A third column with the galaxy map and the history charts
+    Wide,
+}
+
+impl LayoutMode {
+    fn for_area(area: Rect) -> Self {
+        if area.width < COMPACT_WIDTH {
+            LayoutMode::Compact
+        } else if area.width >= WIDE_WIDTH && area.height >= WIDE_HEIGHT {
+            LayoutMode::Wide
+        } else {
+            LayoutMode::Normal
+        }
+    }
+}
 
 pub(crate) fn render_game_ui(app: &mut App, frame: &mut Frame) {
-    // Layout principale: 2 righe (Header | Main)
+    let mode = LayoutMode::for_area(frame.area());
+    // Only the overview has an instructions panel, and the compact layout has no room for it
+    let collapsed =
+        app.instructions_collapsed || mode == LayoutMode::Compact || app.tab != Tab::Overview;
+
+    // Layout principale: Header | Tabs | Main, plus the hint bar if the instructions are collapsed
     let outer_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Global variables
-            Constraint::Fill(1),   // Main content
+            Constraint::Length(3),                // Global variables
+            Constraint::Length(1),                // Tab bar
+            Constraint::Fill(1),                  // Main content
+            Constraint::Length(collapsed as u16), // Hint bar
         ])
         .split(frame.area());
+    let area = outer_layout[2];
+
+    /////////// RENDERING SECTIONS //////////////////
+
+    // 1. Global variables and tabs (top), visible in every tab
+    global::render_globals_info(app, frame, outer_layout[0]);
+    tabs::render_tab_bar(app, frame, outer_layout[1]);
+    if collapsed {
+        instructions::render_hint_bar(app, frame, outer_layout[3]);
+    }
+
+    // 2. Content of the current tab
+    match app.tab {
+        Tab::Overview => render_overview(app, frame, area, mode, collapsed),
+        Tab::GalaxyMap => tabs::render_map_tab(app, frame, area),
+        Tab::Planets => tabs::render_planets_tab(app, frame, area),
+        Tab::Explorers => tabs::render_explorers_tab(app, frame, area),
+        Tab::Statistics => tabs::render_statistics_tab(app, frame, area),
+        Tab::Logs => log::render_log_tab(app, frame, area),
+    }
 
-    // Layout principale: 2 colonne (Left 40% | Right 60%)
+    // 3. Log Overlay (se attivo), over the whole view outside the overview
+    if app.show_log_overlay
+        && matches!(
+            app.tab,
+            Tab::GalaxyMap | Tab::Planets | Tab::Explorers | Tab::Statistics
+        )
+    {
+        log::render_log_overlay(app, frame, area);
+    }
+}
+
+/// Everything at a glance: tables, selected planet, galaxy map and instructions
+fn render_overview(
+    app: &mut App,
+    frame: &mut Frame,
+    area: Rect,
+    mode: LayoutMode,
+    collapsed: bool,
+) {
+    // Explorers panel: one row per explorer, plus borders and header
+    let explorers_height = (app.explorers_info.len() as u16 + 3).min(area.height * 2 / 5);
+
+    if mode == LayoutMode::Compact {
+        let column = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(explorers_height),
+                Constraint::Min(5),         // Planets
+                Constraint::Percentage(35), // Extra info
+            ])
+            .split(area);
+        explorers::render_explorers(app, frame, column[0]);
+        planets::render_planets_table(app, frame, column[1]);
+        render_extra_info_planet(app, frame, column[2]);
+        if app.show_log_overlay {
+            log::render_log_overlay(app, frame, area);
+        }
+        return;
+    }
+
+    // Layout principale: colonne (Left | Right), or (Left | Middle | Right) when wide
     let main_layout = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(40), // Left column (Explorers + Planets)
-            Constraint::Percentage(60), // Right column (Extra + Instructions)
-        ])
-        .split(outer_layout[1]);
+        .constraints(match mode {
+            LayoutMode::Wide => vec![
+                Constraint::Percentage(30), // Explorers + Planets
+                Constraint::Percentage(35), // Extra + Instructions
+                Constraint::Percentage(35), // Map + Charts
+            ],
+            _ => vec![
+                Constraint::Percentage(40), // Left column (Explorers + Planets)
+                Constraint::Percentage(60), // Right column (Extra + Instructions)
+            ],
+        })
+        .split(area);
 
     // Left column: Explorers sopra, Planets sotto
     let left_column = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(6), // Explorers (max 2, compatti)
-            Constraint::Fill(1),   // Planets (scrollabile)
+            Constraint::Length(explorers_height), // Explorers
+            Constraint::Fill(1),                  // Planets (scrollabile)
         ])
         .split(main_layout[0]);
 
     // Right column: Extra Info sopra, Instructions sotto
+    // Collapsed instructions leave the space to the extra info, or to the scenario
+    let bottom_panel = if collapsed && app.scenario.is_none() {
+        Constraint::Length(0)
+    } else {
+        Constraint::Percentage(40)
+    };
     let right_column = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(60), // Extra info
-            Constraint::Percentage(40), // Instructions
+            Constraint::Fill(1), // Extra info
+            bottom_panel,        // Instructions
         ])
         .split(main_layout[1]);
 
-    /////////// RENDERING SECTIONS //////////////////
-
-    // 1. Global variables (top)
-    global::render_globals_info(app, frame, outer_layout[0]);
-
-    // 2. Explorers (top left)
+    // 1. Explorers (top left)
     explorers::render_explorers(app, frame, left_column[0]);
 
-    // 3. Planets (bottom left)
+    // 2. Planets (bottom left)
     planets::render_planets_table(app, frame, left_column[1]);
 
-    // 4. Extra Info (top right)
-    render_extra_info_planet(app, frame, right_column[0]);
+    // 3. Extra Info and galaxy map: side by side, or the map in its own column with the charts
+    let map_area = if mode == LayoutMode::Wide {
+        let map_column = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(main_layout[2]);
+        render_extra_info_planet(app, frame, right_column[0]);
+        charts::render_history_charts(app, frame, map_column[1]);
+        map_column[0]
+    } else {
+        let extra_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(right_column[0]);
+        render_extra_info_planet(app, frame, extra_layout[0]);
+        extra_layout[1]
+    };
+    render_map(app, frame, map_area);
 
-    // 5. Instructions (bottom right)
-    instructions::render_instructions(app, frame, right_column[1]);
+    // 4. Instructions (bottom right), next to the scenario schedule if any
+    if collapsed {
+        scenario::render_scenario_schedule(app, frame, right_column[1]);
+    } else if app.scenario.is_some() {
+        let bottom_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(right_column[1]);
+        instructions::render_instructions(app, frame, bottom_layout[0]);
+        scenario::render_scenario_schedule(app, frame, bottom_layout[1]);
+    } else {
+        instructions::render_instructions(app, frame, right_column[1]);
+    }
 
-    // 6. Log Overlay (se attivo, copre solo la colonna destra)
+    // 5. Log Overlay (se attivo, copre le colonne a destra)
     if app.show_log_overlay {
-        log::render_log_overlay(app, frame, main_layout[1]);
+        let log_area = main_layout[1].union(main_layout[main_layout.len() - 1]);
+        log::render_log_overlay(app, frame, log_area);
     }
 }
 
+/// Galaxy map with the selected planet highlighted, clickable
+fn render_map(app: &mut App, frame: &mut Frame, area: Rect) {
+    let ids: Vec<u32> = app.planets_info.iter().map(|(id, _)| *id).collect();
+    // The highlighted label is only a colour, so name it in the title too
+    let title = match app.selected_planet_id() {
+        Some(id) if app.accessible => format!("Galaxy - selected planet {}", id),
+        _ => "Galaxy".to_string(),
+    };
+    galaxy_map::render_galaxy_map(
+        frame,
+        area,
+        &title,
+        &ids,
+        &app.topology_links(),
+        app.selected_planet_id(),
+        &app.theme(),
+    );
+    app.click_areas.galaxy_map = area;
+}
+
+/// Renders the selected planet and, below it, its energy cells one by one
 fn render_extra_info_planet(app: &App, frame: &mut Frame, area: Rect) {
-    let text = vec![
+    let theme = app.theme();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(3), Constraint::Fill(2)])
+        .split(area);
+    let area = layout[0];
+    let selected = app.selected_planet_id();
+    let planet_type = selected.and_then(|id| app.planet_type(id));
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("  {}: ", label), Style::default().fg(theme.label)),
+            Span::styled(value, Style::default()),
+        ])
+    };
+
+    let mut text = vec![
         Line::from(""),
         Line::from(Span::styled(
             "  Select Entity",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  Name Planet: ", Style::default().fg(Color::Gray)),
+            Span::styled("  Name Planet: ", Style::default().fg(theme.label)),
             Span::styled(
                 format!("{}", app.get_name_selected_planet()),
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
         ]),
-        Line::from(""),
         Line::from(vec![
-            Span::styled("  Planet ID: ", Style::default().fg(Color::Gray)),
+            Span::styled("  Planet ID: ", Style::default().fg(theme.label)),
             Span::styled(app.get_id_selected_planet(), Style::default()),
         ]),
-        Line::from(""),
+        field(
+            "Type",
+            planet_type.map_or("None".to_string(), |t| t.to_string()),
+        ),
         Line::from(vec![
-            Span::styled("  Charged Cells: ", Style::default().fg(Color::Gray)),
+            Span::styled("  Charged Cells: ", Style::default().fg(theme.label)),
             Span::styled(app.get_cells_info_selected_planet(), Style::default()),
         ]),
-        Line::from(""),
         Line::from(vec![
-            Span::styled("  Rocket: ", Style::default().fg(Color::Gray)),
+            Span::styled("  Rocket: ", Style::default().fg(theme.label)),
             Span::styled(
                 format!("{}", app.get_rocket_of_selected_planet()),
                 Style::default(),
@@ -113,10 +286,171 @@ fn render_extra_info_planet(app: &App, frame: &mut Frame, area: Rect) {
         ]),
     ];
 
-    let paragraph = Paragraph::new(text).block(
+    // What the planet type allows, as long as the galaxy file tells the type
+    if let Some(planet_type) = planet_type {
+        text.push(field(
+            "Builds Rockets",
+            if planet_type.can_build_rockets() {
+                "yes"
+            } else {
+                "no"
+            }
+            .to_string(),
+        ));
+        text.push(field(
+            "Basic Resources",
+            planet_type.basic_resources().to_string(),
+        ));
+        text.push(field(
+            "Combinations",
+            planet_type.combination_rules().to_string(),
+        ));
+    }
+
+    if let Some(id) = selected {
+        let neighbours: Vec<String> = app
+            .planets_info
+            .iter()
+            .filter(|(other, _)| app.linked(id, **other))
+            .map(|(other, info)| format!("{} ({:?})", other, info.name))
+            .collect();
+        let explorers: Vec<String> = app
+            .explorers_info
+            .iter()
+            .filter(|(_, info)| info.current_planet_id == id)
+            .map(|(explorer, _)| explorer.to_string())
+            .collect();
+        text.push(field("Neighbours", join_or_none(neighbours)));
+        text.push(field("Explorers Here", join_or_none(explorers)));
+    }
+
+    text.push(Line::from(vec![
+        Span::styled("  Explorer: ", Style::default().fg(theme.label)),
+        Span::styled(app.get_selected_explorer(), Style::default()),
+    ]));
+
+    if let Some(id) = selected {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "  Recent Log",
+            Style::default()
+                .fg(theme.header)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let lines = app.entity_log_lines(Entity::Planet(id), 3);
+        if lines.is_empty() {
+            text.push(Line::from(Span::styled(
+                "  Nothing yet",
+                Style::default().fg(theme.muted),
+            )));
+        }
+        for (level, message) in lines {
+            text.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<5} ", level),
+                    Style::default().fg(theme.log_level(level)),
+                ),
+                Span::styled(message, Style::default().fg(theme.text)),
+            ]));
+        }
+    }
+
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false }).block(
         Block::bordered()
             .title(" Extra Info ")
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.border)),
     );
     frame.render_widget(paragraph, area);
+    render_energy_cells(app, frame, layout[1]);
+}
+
+/// Items joined with commas, "None" if there are none
+fn join_or_none(items: Vec<String>) -> String {
+    if items.is_empty() {
+        "None".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+/// Renders a bar of the selected planet's energy, then the state and history of each cell
+///
+/// Cells that don't fit are summed up in a last line.
+fn render_energy_cells(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let block = Block::bordered()
+        .title(" Energy Cells ")
+        .border_style(Style::default().fg(theme.border));
+    let planet = app
+        .selected_planet_id()
+        .and_then(|id| Some((app.planets_info.get_info(id)?, app.stats.cells.get(&id))));
+
+    let mut text = Vec::new();
+    match planet {
+        Some((info, cells)) => {
+            let inner = block.inner(area);
+            let width = inner.width.saturating_sub(2).max(1) as usize;
+            let mut bar = planets::energy_bar(
+                info.charged_cells_count,
+                info.energy_cells.len(),
+                width,
+                &theme,
+            );
+            bar.spans.insert(0, Span::raw(" "));
+            text.push(bar);
+            text.push(Line::from(""));
+
+            // Below the bar and the empty line, keeping the last row for the summary if needed
+            let rows = inner.height.saturating_sub(2) as usize;
+            let shown = if info.energy_cells.len() > rows {
+                rows.saturating_sub(1)
+            } else {
+                rows
+            };
+
+            for (i, charged) in info.energy_cells.iter().enumerate().take(shown) {
+                let stats = cells
+                    .and_then(|cells| cells.get(i))
+                    .copied()
+                    .unwrap_or_default();
+                let (symbol, state, color) = if *charged {
+                    ("■", "on ", theme.energy_charged)
+                } else {
+                    ("□", "off", theme.energy_empty)
+                };
+                let since = match stats
+                    .last_charged
+                    .map(|tick| app.stats.tick.saturating_sub(tick))
+                {
+                    None => "never charged".to_string(),
+                    Some(1) => "charged 1 tick ago".to_string(),
+                    Some(ticks) => format!("charged {} ticks ago", ticks),
+                };
+                text.push(Line::from(vec![
+                    Span::styled(format!(" {:>2} ", i), Style::default().fg(theme.label)),
+                    Span::styled(format!("{} {} ", symbol, state), Style::default().fg(color)),
+                    Span::styled(format!("{:<22}", since), Style::default().fg(theme.text)),
+                    Span::styled(
+                        format!("+{} -{}", stats.charges, stats.discharges),
+                        Style::default().fg(theme.muted),
+                    ),
+                ]));
+            }
+
+            let hidden = &info.energy_cells[shown.min(info.energy_cells.len())..];
+            if !hidden.is_empty() {
+                let charged = hidden.iter().filter(|charged| **charged).count();
+                text.push(Line::from(Span::styled(
+                    format!(" +{} more, {} charged", hidden.len(), charged),
+                    Style::default().fg(theme.muted),
+                )));
+            }
+        }
+        None => text.push(Line::from(Span::styled(
+            "  Select a planet to see its cells",
+            Style::default().fg(theme.muted),
+        ))),
+    }
+
+    frame.render_widget(Paragraph::new(text).block(block), area);
 }
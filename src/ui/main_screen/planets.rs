@@ -1,51 +1,132 @@
-use omc_galaxy::Status;
 use ratatui::{
     Frame,
     layout::{Constraint, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Cell, Row, Table},
 };
 
 use crate::app::App;
+use crate::planet_table::{PlanetFilter, SortKey};
+use crate::theme::Theme;
+use crate::ui::{highlight_style, highlight_symbol, status_text};
 
+/// Widest energy bar of the planets table, planets with more cells get a scaled one
+const ENERGY_BAR_WIDTH: usize = 10;
+
+/// `charged` of `total` cells as ■ and □, one per cell if they fit in `width`
+///
+/// Otherwise the bar is scaled down to `width`, still showing at least one ■
+/// when some cell is charged and one □ when some cell is empty.
+pub(crate) fn energy_bar(
+    charged: usize,
+    total: usize,
+    width: usize,
+    theme: &Theme,
+) -> Line<'static> {
+    let (charged, empty) = if total <= width {
+        (charged, total - charged)
+    } else {
+        let mut scaled = (charged * width + total / 2) / total;
+        if charged > 0 {
+            scaled = scaled.max(1);
+        }
+        if charged < total {
+            scaled = scaled.min(width - 1);
+        }
+        (scaled, width - scaled)
+    };
+    Line::from(vec![
+        Span::styled(
+            "■".repeat(charged),
+            Style::default().fg(theme.energy_charged),
+        ),
+        Span::styled("□".repeat(empty), Style::default().fg(theme.energy_empty)),
+    ])
+}
+
+/// Renders the planets in the order and with the filter of `app.planet_view`
+///
+/// The column the table is sorted by has an arrow in its header.
 pub fn render_planets_table(app: &mut App, frame: &mut Frame, area: Rect) {
-    let header = Row::new(vec!["ID", "Rocket", "Energy", "Status", "Incoming"]).style(
+    let theme = app.theme();
+    let view = app.planet_view;
+    let header = Row::new(
+        [
+            Some(SortKey::Id),
+            Some(SortKey::Rocket),
+            Some(SortKey::Energy),
+            Some(SortKey::Status),
+            Some(SortKey::Neighbours),
+            None,
+        ]
+        .map(|key| match key {
+            Some(key) if key == view.sort => {
+                format!(
+                    "{} {}",
+                    key.title(),
+                    if view.descending { "▼" } else { "▲" }
+                )
+            }
+            Some(key) => key.title().to_string(),
+            None => "Incoming".to_string(),
+        }),
+    )
+    .style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.header)
             .add_modifier(Modifier::BOLD),
     );
 
-    // TODO: discriminate between the number of energy cells
-    let rows: Vec<Row> = app
-        .planets_info
+    let selected = app.selected_planet_id();
+    let planet_rows = app.planet_rows();
+    let rows: Vec<Row> = planet_rows
         .iter()
+        .copied()
+        .filter_map(|id| app.planets_info.get_info(id).map(|info| (id, info)))
         .map(|(id, info)| {
-            let energy_str = "■".repeat(info.charged_cells_count)
-                + &"□".repeat(info.energy_cells.len() - info.charged_cells_count);
+            let energy = if app.accessible {
+                Line::from(format!(
+                    "{}/{}",
+                    info.charged_cells_count,
+                    info.energy_cells.len()
+                ))
+            } else {
+                energy_bar(
+                    info.charged_cells_count,
+                    info.energy_cells.len(),
+                    ENERGY_BAR_WIDTH,
+                    &theme,
+                )
+            };
 
             // Row style: write in Green if it is a neighbours of the selected planet
-            let row_style = match app.table_state.selected() {
-                Some(selected) => {
-                    if app.galaxy_topology[*id as usize][selected] {
-                        Style::default().fg(Color::Green).bold()
-                    } else {
-                        Style::default()
-                    }
-                }
-                None => Style::default(),
+            let neighbour = selected.is_some_and(|selected| app.linked(id, selected));
+            let links = app
+                .galaxy_topology
+                .get(id as usize)
+                .map_or(0, |row| row.iter().filter(|linked| **linked).count());
+            let row_style = if neighbour {
+                Style::default().fg(theme.success).bold()
+            } else {
+                Style::default()
             };
-
-            let status = match info.status {
-                Status::Running => "Running",
-                Status::Paused => "Paused",
-                Status::Dead => "Dead",
+            // Without colours the neighbours are marked next to their ID
+            let id = if app.accessible && neighbour {
+                format!("{}*", id)
+            } else {
+                id.to_string()
             };
 
+            let status = status_text(&info.status);
+
             Row::new(vec![
-                Cell::from(id.to_string()),
+                Cell::from(id),
                 Cell::from(info.rocket.to_string()),
-                Cell::from(energy_str),
-                Cell::from(status.to_string()),
+                Cell::from(energy),
+                Cell::from(status.to_string())
+                    .style(Style::default().fg(theme.status(&info.status))),
+                Cell::from(links.to_string()),
                 Cell::from("-".to_string()),
             ])
             .style(row_style)
@@ -55,22 +136,39 @@ pub fn render_planets_table(app: &mut App, frame: &mut Frame, area: Rect) {
     let table = Table::new(
         rows,
         [
-            Constraint::Length(4),
-            Constraint::Min(7),
-            Constraint::Min(7),
-            Constraint::Min(7),
+            Constraint::Length(5),
+            Constraint::Min(8),
+            Constraint::Min(8),
+            Constraint::Min(8),
             Constraint::Min(7),
+            Constraint::Min(8),
         ],
     )
     .header(header)
     .block(
         Block::bordered()
-            .title(" Planets ")
-            .border_style(Style::default().fg(Color::Green)),
+            .title(format!(
+                " Planets{}{} ",
+                match view.filter {
+                    PlanetFilter::All => String::new(),
+                    filter => format!(" - {}", filter.title()),
+                },
+                if app.accessible {
+                    " (* = linked to selected)"
+                } else {
+                    ""
+                }
+            ))
+            .border_style(Style::default().fg(theme.border)),
     )
     // AGGIUNTA: Definiamo lo stile della riga selezionata centralmente
-    .row_highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White));
+    .row_highlight_style(highlight_style(&theme, app.accessible))
+    .highlight_symbol(highlight_symbol(app.accessible));
 
     // CAMBIO: Usa render_stateful_widget invece di render_widget
+    // The highlighted row follows the selected planet wherever the order puts it
+    app.table_state
+        .select(app.selected_planet_row(&planet_rows));
     frame.render_stateful_widget(table, area, &mut app.table_state);
+    app.click_areas.planets_table = area;
 }
@@ -0,0 +1,52 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+
+use crate::app::App;
+
+/// Renders the upcoming actions of the running scenario
+pub(crate) fn render_scenario_schedule(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let Some(scenario) = &app.scenario else {
+        return;
+    };
+
+    let mut text = vec![Line::from(Span::styled(
+        format!(
+            "  {} done, {} to go{}",
+            scenario.executed(),
+            scenario.upcoming().len(),
+            if scenario.random_events {
+                ""
+            } else {
+                ", no random events"
+            }
+        ),
+        Style::default().fg(theme.label),
+    ))];
+    text.extend(scenario.upcoming().iter().map(|scheduled| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:>5} ", scheduled.tick),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                scheduled.action.to_string(),
+                Style::default().fg(theme.text),
+            ),
+        ])
+    }));
+
+    let paragraph = Paragraph::new(text).block(
+        Block::bordered()
+            .title(format!(" Scenario: {} ", scenario.name))
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,184 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::Tabs,
+};
+
+use super::{explorers, planets, render_extra_info_planet, render_map};
+use crate::app::App;
+use crate::keymap::{Action, ActionGroup};
+use crate::ui::end_screen;
+
+/// Views of the game screen, switched with the number keys
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) enum Tab {
+    #[default]
+    Overview,
+    GalaxyMap,
+    Planets,
+    Explorers,
+    Statistics,
+    Logs,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 6] = [
+        Tab::Overview,
+        Tab::GalaxyMap,
+        Tab::Planets,
+        Tab::Explorers,
+        Tab::Statistics,
+        Tab::Logs,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Overview => "Overview",
+            Tab::GalaxyMap => "Galaxy Map",
+            Tab::Planets => "Planets",
+            Tab::Explorers => "Explorers",
+            Tab::Statistics => "Statistics",
+            Tab::Logs => "Logs",
+        }
+    }
+
+    /// Action switching to this tab, whose key is shown in the tab bar
+    pub fn action(self) -> Action {
+        match self {
+            Tab::Overview => Action::ShowOverview,
+            Tab::GalaxyMap => Action::ShowGalaxyMap,
+            Tab::Planets => Action::ShowPlanets,
+            Tab::Explorers => Action::ShowExplorers,
+            Tab::Statistics => Action::ShowStatistics,
+            Tab::Logs => Action::ShowLogs,
+        }
+    }
+
+    /// Whether the tab shows the panel the actions of `group` are about
+    ///
+    /// Actions that aren't about a panel belong to every tab.
+    pub fn shows(self, group: ActionGroup) -> bool {
+        match group {
+            ActionGroup::Planets => matches!(self, Tab::Overview | Tab::GalaxyMap | Tab::Planets),
+            ActionGroup::Explorers => matches!(self, Tab::Overview | Tab::Explorers),
+            _ => true,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|tab| *tab == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Renders the tab titles with their keys, recording where each one is for the mouse
+pub(crate) fn render_tab_bar(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let titles: Vec<String> = Tab::ALL
+        .iter()
+        .map(|tab| {
+            let key = app
+                .keymap
+                .keys_for(tab.action())
+                .first()
+                .map(|key| format!("{} ", key))
+                .unwrap_or_default();
+            // The selected tab is bracketed, for when its colours don't show
+            if app.accessible && *tab == app.tab {
+                format!("[{}{}]", key, tab.title())
+            } else {
+                format!("{}{}", key, tab.title())
+            }
+        })
+        .collect();
+
+    // Each title is padded by a space on both sides, then comes the divider
+    let mut x = area.x;
+    for (i, title) in titles.iter().enumerate() {
+        let width = Line::from(title.as_str()).width() as u16 + 2;
+        app.click_areas.tabs[i] = Rect::new(x, area.y, width, 1).intersection(area);
+        x = x.saturating_add(width + 1);
+    }
+
+    let selected = Tab::ALL.iter().position(|tab| *tab == app.tab);
+    let tabs = Tabs::new(titles)
+        .select(selected)
+        .style(Style::default().fg(theme.label))
+        .highlight_style(
+            Style::default()
+                .fg(theme.selected_fg)
+                .bg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    frame.render_widget(tabs, area);
+}
+
+/// Full-size galaxy map, with the selected planet next to it
+pub(crate) fn render_map_tab(app: &mut App, frame: &mut Frame, area: Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Max(40)])
+        .split(area);
+    render_map(app, frame, layout[0]);
+    render_extra_info_planet(app, frame, layout[1]);
+}
+
+/// Planets table using the whole height, with the selected planet next to it
+pub(crate) fn render_planets_tab(app: &mut App, frame: &mut Frame, area: Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Max(40)])
+        .split(area);
+    planets::render_planets_table(app, frame, layout[0]);
+    render_extra_info_planet(app, frame, layout[1]);
+}
+
+/// Explorers table using the whole height, with the selected explorer next to it
+pub(crate) fn render_explorers_tab(app: &mut App, frame: &mut Frame, area: Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Max(40)])
+        .split(area);
+    explorers::render_explorers(app, frame, layout[0]);
+    explorers::render_explorer_details(app, frame, layout[1]);
+}
+
+/// Statistics of the game so far, the same the end screen shows
+pub(crate) fn render_statistics_tab(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+    let left_column = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Fill(1)])
+        .split(layout[0]);
+    let right_column = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(layout[1]);
+
+    end_screen::render_totals(app, frame, left_column[0]);
+    end_screen::render_planet_deaths(app, frame, left_column[1]);
+    end_screen::render_history_chart(
+        frame,
+        right_column[0],
+        " Alive Planets Over Time ",
+        "alive planets",
+        &app.stats.alive_history,
+        theme.alive,
+        &theme,
+    );
+    end_screen::render_history_chart(
+        frame,
+        right_column[1],
+        " Total Energy Over Time ",
+        "charged cells",
+        &app.stats.energy_history,
+        theme.energy_charged,
+        &theme,
+    );
+}
@@ -1,35 +1,259 @@
+mod editor;
+mod end_screen;
+pub(crate) mod galaxy_map;
 mod main_screen;
+mod overlays;
 mod screens;
 
-use ratatui::Frame;
+use ratatui::{
+    Frame,
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Paragraph, Wrap},
+};
+
+use omc_galaxy::Status;
 
 use crate::app::App;
 use crate::game_state::GameState;
+use crate::keymap::Action;
+use crate::theme::Theme;
+
+pub(crate) use main_screen::Tab;
+
+/// Smallest terminal the screens are drawn in
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 12;
 
 /// Main UI rendering dispatcher
 ///
 /// Routes to the appropriate screen based on current game state:
 /// - WaitingStart: Shows start screen
+/// - Editing: Shows the galaxy file editor
 /// - Running: Shows main game UI
-/// - Paused: Shows main game UI (pause overlay can be added if needed)
-/// - Ended: Shows main game UI with end state
+/// - Paused: Shows main game UI with the pause overlay on top
+/// - Ended: Shows the end-of-game summary
+///
+/// Toasts of the latest game events go over the game UI. The help popup and
+/// the command palette, when open, are drawn over any screen.
+/// In accessibility mode the finished frame is converted to plain ASCII.
 pub fn render_ui(app: &mut App, frame: &mut Frame) {
+    // Areas are recorded again by whatever gets drawn this frame
+    app.click_areas = ClickAreas::default();
+    let theme = app.theme();
+    frame.render_widget(
+        Block::default().style(Style::default().bg(theme.background).fg(theme.text)),
+        frame.area(),
+    );
+
+    // Better a message than garbled widgets
+    let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        let message = Paragraph::new(vec![
+            Line::from("Terminal too small"),
+            Line::from(format!(
+                "{}x{}, need {}x{}",
+                area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+            )),
+        ])
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(theme.warning));
+        frame.render_widget(message, centered_rect(area.width, 2, area));
+        return;
+    }
+
     match app.get_game_state() {
         GameState::WaitingStart => {
             // Show start screen
             screens::render_start_screen(app, frame);
         }
+        GameState::Editing => {
+            // Show the galaxy editor (state is created when entering Editing)
+            if let Some(galaxy_editor) = &app.editor {
+                editor::render_editor_screen(galaxy_editor, &theme, frame);
+            }
+        }
         GameState::Running => {
             // Show normal game UI
             main_screen::render_game_ui(app, frame);
+            if app.sunray_entry.is_some() {
+                overlays::render_sunray_control(app, frame);
+            }
+            if app.show_console {
+                overlays::render_console(app, frame);
+            }
+            overlays::render_toasts(app, frame);
         }
         GameState::Paused => {
             // Show game UI with pause overlay
             main_screen::render_game_ui(app, frame);
+            overlays::render_pause_overlay(app, frame);
+            if app.sunray_entry.is_some() {
+                overlays::render_sunray_control(app, frame);
+            }
+            if app.show_console {
+                overlays::render_console(app, frame);
+            }
+            overlays::render_toasts(app, frame);
         }
         GameState::Ended => {
-            // Show game UI in ended state
-            main_screen::render_game_ui(app, frame);
+            // Show the end-of-game summary
+            end_screen::render_end_screen(app, frame);
         }
     }
+
+    // Help and the command palette can be opened over any screen
+    if app.show_help {
+        overlays::render_help_popup(app, frame);
+    }
+    if app.palette.is_some() {
+        overlays::render_palette(app, frame);
+    }
+    if app.description.is_some() {
+        overlays::render_description(app, frame);
+    }
+    if app.show_notifications {
+        overlays::render_notification_history(app, frame);
+    }
+
+    if app.accessible {
+        to_ascii(frame.buffer_mut());
+    }
+}
+
+/// Replaces box drawing and graphics characters with ASCII ones
+///
+/// Screen readers spell out or skip them, and borders must not be the only
+/// cue for anything anyway.
+fn to_ascii(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        let replacement = match cell.symbol().chars().next() {
+            Some('─' | '━' | '═' | '╌' | '┄') => "-",
+            Some('│' | '┃' | '║' | '╎' | '┆') => "|",
+            Some('┌' | '┐' | '└' | '┘' | '╭' | '╮' | '╰' | '╯' | '├' | '┤' | '┬' | '┴' | '┼') => {
+                "+"
+            }
+            Some('╔' | '╗' | '╚' | '╝' | '╠' | '╣' | '╦' | '╩' | '╬' | '┏' | '┓' | '┗' | '┛') => {
+                "+"
+            }
+            Some('█' | '▉' | '▊' | '▋' | '▌' | '▍' | '▎' | '▏' | '■') => "#",
+            Some('▁' | '▂' | '▃' | '▄' | '▅' | '▆' | '▇') => "_",
+            Some('□') => "o",
+            Some('•' | '·') => ".",
+            Some('↑' | '▲') => "^",
+            Some('↓' | '▼') => "v",
+            Some('←') => "<",
+            Some('→') => ">",
+            Some('\u{2800}') => " ",
+            Some('\u{2801}'..='\u{28FF}') => ".",
+            _ => continue,
+        };
+        cell.set_symbol(replacement);
+    }
+}
+
+/// Where the clickable widgets were drawn in the last frame, for mouse hit-testing
+///
+/// Widgets not drawn in the last frame keep an empty area.
+#[derive(Default, Clone, Copy)]
+pub(crate) struct ClickAreas {
+    pub galaxy_files: Rect,
+    pub planets_table: Rect,
+    pub explorers_table: Rect,
+    pub galaxy_map: Rect,
+    pub log_overlay: Rect,
+    /// Popups drawn over the screen, clicks on them don't reach what is below
+    pub pause_popup: Rect,
+    pub sunray_control: Rect,
+    /// `Game: <state>` in the header, clicking it pauses or resumes
+    pub game_state: Rect,
+    /// Sunray gauge in the header
+    pub sunray: Rect,
+    /// Titles in the tab bar, in the order of `Tab::ALL`
+    pub tabs: [Rect; Tab::ALL.len()],
+}
+
+/// Index of the list or table row under the given terminal row, if any
+///
+/// `first_row` is the screen row of the first item, `offset` the index of
+/// the first visible item.
+pub(crate) fn row_at(area: Rect, first_row: u16, offset: usize, row: u16) -> Option<usize> {
+    let last_row = area.bottom().saturating_sub(1); // Bottom border
+    if row < first_row || row >= last_row {
+        return None;
+    }
+    Some(offset + (row - first_row) as usize)
+}
+
+/// Returns a rectangle of the given size centered in `area`, shrunk to fit if needed
+pub(crate) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Style of the selected row of a table or list
+///
+/// Reversed too in accessibility mode, so the selection shows without colours.
+pub(crate) fn highlight_style(theme: &Theme, accessible: bool) -> Style {
+    let style = Style::default()
+        .bg(theme.highlight_bg)
+        .fg(theme.highlight_fg);
+    if accessible {
+        style.add_modifier(Modifier::REVERSED)
+    } else {
+        style
+    }
+}
+
+/// Marker in front of the selected row, only in accessibility mode
+pub(crate) fn highlight_symbol(accessible: bool) -> &'static str {
+    if accessible { "> " } else { "" }
+}
+
+/// Name of a planet or explorer status, as shown in the tables and described
+pub(crate) fn status_text(status: &Status) -> &'static str {
+    match status {
+        Status::Running => "Running",
+        Status::Paused => "Paused",
+        Status::Dead => "Dead",
+    }
+}
+
+/// Colour of the key label of an action, grouping related actions
+pub(crate) fn action_color(action: Action, theme: &Theme) -> Color {
+    match action {
+        Action::Quit | Action::CancelResume => theme.error,
+        Action::TogglePause | Action::ResumeNow => theme.warning,
+        Action::SelectPreviousPlanet
+        | Action::SelectNextPlanet
+        | Action::CyclePlanetSort
+        | Action::ReversePlanetSort
+        | Action::CyclePlanetFilter => theme.accent,
+        Action::ToggleLog
+        | Action::ScrollLogUp
+        | Action::ScrollLogDown
+        | Action::FilterLog
+        | Action::OpenConsole
+        | Action::OpenPalette
+        | Action::ToggleHelp
+        | Action::ToggleInstructions
+        | Action::CycleTheme
+        | Action::ToggleAccessibility
+        | Action::DescribeSelection => theme.success,
+        Action::SunrayUp
+        | Action::SunrayDown
+        | Action::SunrayUpCoarse
+        | Action::SunrayDownCoarse
+        | Action::EnterSunray => theme.special,
+        _ => theme.text,
+    }
 }
@@ -0,0 +1,472 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Gauge, Padding, Paragraph, Wrap},
+};
+
+use crate::app::{App, RESUME_COUNTDOWN};
+use crate::console::OutputKind;
+use crate::game_state::GameState;
+use crate::keymap::{Action, ActionGroup};
+use crate::theme::Theme;
+use crate::ui::{action_color, centered_rect};
+
+/// Dims everything already drawn in `area`, so a popup stands out over it
+fn dim_background(frame: &mut Frame, area: Rect) {
+    frame
+        .buffer_mut()
+        .set_style(area, Style::default().add_modifier(Modifier::DIM));
+}
+
+/// Renders the pause popup over the game UI
+///
+/// Lists what can be done while paused, or the countdown before resuming.
+pub fn render_pause_overlay(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme();
+    dim_background(frame, frame.area());
+
+    let key_line = |action: Action| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:<6}", app.keymap.label(action)),
+                Style::default()
+                    .fg(action_color(action, &theme))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("- {}", action.description()),
+                Style::default().fg(theme.text),
+            ),
+        ])
+    };
+
+    let mut lines = vec![Line::from("")];
+    match app.resume_countdown {
+        Some(started) => {
+            let left = RESUME_COUNTDOWN.saturating_sub(started.elapsed());
+            lines.push(
+                Line::from(Span::styled(
+                    format!("Resuming in {}...", left.as_secs() + 1),
+                    Style::default()
+                        .fg(theme.success)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Center),
+            );
+            lines.push(Line::from(""));
+            lines.push(key_line(Action::ResumeNow));
+            lines.push(key_line(Action::CancelResume));
+        }
+        None => lines.extend(
+            app.keymap
+                .available(&GameState::Paused)
+                .into_iter()
+                .filter(|action| !matches!(action, Action::ResumeNow | Action::CancelResume))
+                .map(key_line),
+        ),
+    }
+
+    let area = centered_rect(40, lines.len() as u16 + 3, frame.area());
+    let popup = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(" PAUSED ")
+            .title_alignment(Alignment::Center)
+            .border_style(
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+    app.click_areas.pause_popup = area;
+}
+
+/// Renders the sunray probability entry, opened from its key or by clicking the header gauge
+pub fn render_sunray_control(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme();
+    let entry = app.sunray_entry.clone().unwrap_or_default();
+    let area = centered_rect(40, 8, frame.area());
+    let block = Block::bordered()
+        .title(" Sunray Probability ")
+        .title_alignment(Alignment::Center)
+        .border_style(Style::default().fg(theme.special));
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Gauge
+            Constraint::Length(1),
+            Constraint::Length(1), // Entry
+            Constraint::Length(1),
+            Constraint::Fill(1), // Hints
+        ])
+        .split(inner);
+
+    frame.render_widget(sunray_gauge(app.probability_sunray, &theme), layout[0]);
+
+    let entry_line = Line::from(vec![
+        Span::styled(" New value: ", Style::default().fg(theme.label)),
+        Span::styled(
+            format!("{:_<3}", entry),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" %", Style::default().fg(theme.label)),
+    ]);
+    frame.render_widget(Paragraph::new(entry_line), layout[2]);
+
+    let hints = vec![
+        Line::from(Span::styled(
+            " 0-9 type  ENTER apply  ESC close",
+            Style::default().fg(theme.muted),
+        )),
+        Line::from(Span::styled(
+            " ↑↓ ±1  PgUp/PgDn ±10  or wheel",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(hints), layout[4]);
+    app.click_areas.sunray_control = area;
+}
+
+/// Gauge of the sunray probability, the rest of the events are asteroids
+pub(crate) fn sunray_gauge(probability: u32, theme: &Theme) -> Gauge<'static> {
+    Gauge::default()
+        .gauge_style(Style::default().fg(theme.sunray).bg(theme.muted))
+        .percent(probability.min(100) as u16)
+        .label(Span::styled(
+            format!("{}% sunray", probability),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ))
+}
+
+/// Renders the scripting console over the bottom of the screen
+pub fn render_console(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let Some(console) = &app.console else {
+        return;
+    };
+    let screen = frame.area();
+    let height = 14.min(screen.height);
+    let area = Rect::new(screen.x, screen.bottom() - height, screen.width, height);
+
+    // Latest output that fits above the input line
+    let visible = area.height.saturating_sub(3) as usize;
+    let skip = console.output.len().saturating_sub(visible);
+    let mut lines: Vec<Line> = console.output[skip..]
+        .iter()
+        .map(|(kind, text)| {
+            let color = match kind {
+                OutputKind::Input => theme.label,
+                OutputKind::Output => theme.text,
+                OutputKind::Error => theme.error,
+            };
+            Line::from(Span::styled(text.clone(), Style::default().fg(color)))
+        })
+        .collect();
+    lines.resize(visible, Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "> ",
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(console.input.clone(), Style::default().fg(theme.text)),
+        Span::styled("█", Style::default().fg(theme.success)),
+    ]));
+
+    let popup = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(" Console (Rhai) - ENTER run, ↑↓ history, ESC close ")
+            .border_style(Style::default().fg(theme.success)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Renders the command palette near the top of the screen
+pub fn render_palette(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let Some(palette) = &app.palette else {
+        return;
+    };
+    let state = app.get_game_state();
+    let matches = palette.matches(&state);
+
+    let screen = frame.area();
+    let height = (matches.len() as u16 + 4).clamp(6, 18);
+    let mut area = centered_rect(56, height, screen);
+    area.y = screen.y + (screen.height.saturating_sub(area.height)).min(2);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                " > ",
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(palette.query.clone(), Style::default().fg(theme.text)),
+            Span::styled("█", Style::default().fg(theme.success)),
+        ]),
+        Line::from(Span::styled(
+            match (palette.panel, palette.scope()) {
+                (_, Some(tab)) => format!("   {} tab actions, TAB for all", tab.title()),
+                (Some(tab), None) => format!("   All actions, TAB for the {} tab", tab.title()),
+                (None, None) => String::new(),
+            },
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "   No matching action",
+            Style::default().fg(theme.muted),
+        )));
+    }
+
+    // Keep the selected action in view
+    let visible = area.height.saturating_sub(4) as usize;
+    let skip = (palette.selected + 1).saturating_sub(visible);
+    let width = area.width.saturating_sub(4) as usize;
+    lines.extend(
+        matches
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible)
+            .map(|(i, action)| {
+                let keys = match app.keymap.keys_for(*action) {
+                    [] => String::new(),
+                    _ => app.keymap.label(*action),
+                };
+                let description = action.description();
+                let padding =
+                    width.saturating_sub(description.chars().count() + keys.chars().count());
+                let style = if i == palette.selected {
+                    Style::default()
+                        .fg(theme.selected_fg)
+                        .bg(theme.selected_bg)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {}{}", description, " ".repeat(padding)), style),
+                    Span::styled(
+                        format!("{} ", keys),
+                        style.fg(if i == palette.selected {
+                            theme.selected_fg
+                        } else {
+                            action_color(*action, &theme)
+                        }),
+                    ),
+                ])
+            }),
+    );
+
+    let popup = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(" Command Palette - ↑↓ choose, ENTER run, ESC close ")
+            .border_style(Style::default().fg(theme.success)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Renders every key binding grouped by context, from the keymap used by the dispatcher
+///
+/// Actions not available in the current state are dimmed.
+pub fn render_help_popup(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let state = app.get_game_state();
+    let width = Action::ALL
+        .iter()
+        .map(|action| app.keymap.label(*action).chars().count())
+        .max()
+        .unwrap_or(1);
+
+    let group_lines = |group: ActionGroup| {
+        let mut lines = vec![Line::from(Span::styled(
+            format!(" {}", group.title()),
+            Style::default()
+                .fg(theme.header)
+                .add_modifier(Modifier::BOLD),
+        ))];
+        lines.extend(
+            Action::ALL
+                .into_iter()
+                .filter(|action| action.group() == group)
+                .map(|action| {
+                    let available = action.is_available(&state);
+                    let (key_style, text_style) = if available {
+                        (
+                            Style::default()
+                                .fg(action_color(action, &theme))
+                                .add_modifier(Modifier::BOLD),
+                            Style::default().fg(theme.text),
+                        )
+                    } else {
+                        (
+                            Style::default().fg(theme.muted),
+                            Style::default().fg(theme.muted),
+                        )
+                    };
+                    Line::from(vec![
+                        Span::styled(
+                            format!("   {:<width$} ", app.keymap.label(action)),
+                            key_style,
+                        ),
+                        Span::styled(action.description(), text_style),
+                    ])
+                }),
+        );
+        lines.push(Line::from(""));
+        lines
+    };
+
+    // Two columns, so that everything fits on usual terminal sizes
+    let (left, right) = ActionGroup::ALL.split_at(2);
+    let left: Vec<Line> = left.iter().flat_map(|group| group_lines(*group)).collect();
+    let right: Vec<Line> = right.iter().flat_map(|group| group_lines(*group)).collect();
+
+    let height = left.len().max(right.len()) as u16 + 3;
+    let area = centered_rect(96, height, frame.area());
+    let block = Block::bordered()
+        .title(" Help - any key to close ")
+        .title_alignment(Alignment::Center)
+        .border_style(Style::default().fg(theme.success));
+    let inner = block.inner(area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(left), columns[0]);
+    frame.render_widget(Paragraph::new(right), columns[1]);
+}
+
+/// Renders the plain text description of the selection, asked for with its key
+pub fn render_description(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let Some(description) = &app.description else {
+        return;
+    };
+
+    // Room for the wrapped text, assuming words don't waste much of each line
+    let area = centered_rect(70, 0, frame.area());
+    let text_width = area.width.saturating_sub(4).max(1) as usize;
+    let height = description.chars().count().div_ceil(text_width) as u16 + 4;
+    let area = centered_rect(area.width, height, frame.area());
+
+    let popup = Paragraph::new(vec![Line::from(""), Line::from(description.as_str())])
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::bordered()
+                .title(" Description - any key to close ")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(theme.success))
+                .padding(Padding::horizontal(1)),
+        );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Renders the toasts of the latest game events in the top right corner, below the header
+///
+/// They don't take the focus, the game goes on under them until they expire.
+pub fn render_toasts(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let screen = frame.area();
+    let width = screen.width.min(44);
+    let mut y = screen.y + 4; // Header and tab bar
+
+    for toast in app.notifications.toasts() {
+        let area = Rect::new(screen.right() - width, y, width, 3).intersection(screen);
+        if area.height < 3 {
+            break;
+        }
+        let color = theme.severity(toast.severity);
+        let popup = Paragraph::new(Line::from(Span::styled(
+            toast.message.as_str(),
+            Style::default().fg(theme.text),
+        )))
+        .block(
+            Block::bordered()
+                .title(format!(
+                    " {} - tick {} ",
+                    toast.severity.label(),
+                    toast.tick
+                ))
+                .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+                .border_style(Style::default().fg(color)),
+        );
+        frame.render_widget(Clear, area);
+        frame.render_widget(popup, area);
+        y += 3;
+    }
+}
+
+/// Renders every notification of the game, newest first
+pub fn render_notification_history(app: &App, frame: &mut Frame) {
+    let theme = app.theme();
+    let area = centered_rect(70, frame.area().height.saturating_sub(4), frame.area());
+
+    let mut lines = vec![Line::from("")];
+    if app.notifications.history.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  Nothing happened yet",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    // Only the newest that fit, the popup isn't scrolled
+    let visible = area.height.saturating_sub(3) as usize;
+    lines.extend(
+        app.notifications
+            .history
+            .iter()
+            .rev()
+            .take(visible)
+            .map(|notification| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("  {:>5}  ", notification.tick),
+                        Style::default().fg(theme.label),
+                    ),
+                    Span::styled(
+                        format!("{:<9}", notification.severity.label()),
+                        Style::default()
+                            .fg(theme.severity(notification.severity))
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        notification.message.as_str(),
+                        Style::default().fg(theme.text),
+                    ),
+                ])
+            }),
+    );
+
+    let popup = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(format!(
+                " Notifications ({}) - any key to close ",
+                app.notifications.history.len()
+            ))
+            .title_alignment(Alignment::Center)
+            .border_style(Style::default().fg(theme.success)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
@@ -1,85 +1,352 @@
+use std::path::Path;
+
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
 
 use crate::app::App;
+use crate::generator::GeneratorForm;
+use crate::keymap::Action;
+use crate::theme::Theme;
+use crate::ui::{centered_rect, galaxy_map, highlight_style};
 
 /// Renders the starting screen with title and instructions
 ///
 /// Shows the game title "One Million Crabs Galaxy" and prompts
-/// to press ENTER to start or Q to quit
-pub fn render_start_screen(app: &App, frame: &mut Frame) {
+/// to press ENTER to start, C to choose a scenario, E to edit the galaxy
+/// file or Q to quit.
+/// If the galaxy failed to load, the error is shown below the prompts.
+/// Below the title, the galaxy files found in the galaxy directory
+/// can be browsed with a preview of the selected one, and new ones
+/// created with the generator popup.
+pub fn render_start_screen(app: &mut App, frame: &mut Frame) {
+    let theme = app.theme();
     let area = frame.area();
 
     // Create a centered layout
     let vertical_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(30),
-            Constraint::Percentage(40),
-            Constraint::Percentage(30),
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
         ])
         .split(area);
 
     let horizontal_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage(20),
-            Constraint::Percentage(60),
-            Constraint::Percentage(20),
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
         ])
         .split(vertical_layout[1]);
 
     let center_area = horizontal_layout[1];
 
     // Title and control instructions
-    let title_text = vec![
+    let mut title_text = vec![
         Line::from(Span::styled(
             "   ONE MILLION CRABS GALAXY       ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(vec![
-            Span::styled("    Press ", Style::default().fg(Color::Gray)),
+            Span::styled("    Press ", Style::default().fg(theme.label)),
             Span::styled(
-                "ENTER",
+                app.keymap.label(Action::Start),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" to ", Style::default().fg(Color::Gray)),
+            Span::styled(" to ", Style::default().fg(theme.label)),
             Span::styled(
                 "START",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("    Press ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!(
+                    "{} {}",
+                    app.keymap.label(Action::PreviousGalaxyFile),
+                    app.keymap.label(Action::NextGalaxyFile)
+                ),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" to ", Style::default().fg(theme.label)),
+            Span::styled(
+                "CHOOSE GALAXY",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("    Press ", Style::default().fg(theme.label)),
+            Span::styled(
+                app.keymap.label(Action::NextScenario),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" to ", Style::default().fg(theme.label)),
+            Span::styled(
+                "CHOOSE SCENARIO",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                match &app.scenario {
+                    Some(scenario) => {
+                        format!(" ({}, {} events)", scenario.name, scenario.actions.len())
+                    }
+                    None => " (none, random events only)".to_string(),
+                },
+                Style::default().fg(theme.label),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("    Press ", Style::default().fg(theme.label)),
+            Span::styled(
+                app.keymap.label(Action::EditGalaxy),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" to ", Style::default().fg(theme.label)),
+            Span::styled(
+                "EDIT GALAXY",
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("    Press ", Style::default().fg(theme.label)),
+            Span::styled(
+                app.keymap.label(Action::GenerateGalaxy),
+                Style::default()
+                    .fg(theme.special)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" to ", Style::default().fg(theme.label)),
+            Span::styled(
+                "GENERATE GALAXY",
+                Style::default()
+                    .fg(theme.special)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("    Press ", Style::default().fg(Color::Gray)),
+            Span::styled("    Press ", Style::default().fg(theme.label)),
             Span::styled(
-                "Q",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                app.keymap.label(Action::Quit),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" to ", Style::default().fg(Color::Gray)),
+            Span::styled(" to ", Style::default().fg(theme.label)),
             Span::styled(
                 "QUIT",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
         ]),
     ];
 
-    let title = Paragraph::new(title_text).alignment(Alignment::Left).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Blue))
-            .style(Style::default()),
+    if let Some(error) = &app.start_error {
+        title_text.push(Line::from(""));
+        title_text.push(Line::from(Span::styled(
+            format!("    {}", error),
+            Style::default().fg(theme.error),
+        )));
+    }
+
+    let title_height = title_text.len() as u16 + 2;
+    let title = Paragraph::new(title_text)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border))
+                .style(Style::default()),
+        );
+
+    // Title on top, file picker below
+    let center_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(title_height), Constraint::Fill(1)])
+        .split(center_area);
+
+    frame.render_widget(title, center_layout[0]);
+    render_galaxy_picker(app, frame, center_layout[1]);
+
+    if let Some(form) = &app.generator_form {
+        render_generator_popup(form, &theme, frame);
+    }
+}
+
+/// Renders the galaxy generator form as a popup over the start screen
+fn render_generator_popup(form: &GeneratorForm, theme: &Theme, frame: &mut Frame) {
+    let rows = form.rows();
+    let area = centered_rect(44, rows.len() as u16 + 7, frame.area());
+
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, value))| {
+            let selected = i == form.field;
+            let value_style = if selected {
+                Style::default()
+                    .fg(theme.selected_fg)
+                    .bg(theme.special)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(" {} {:<16}", if selected { ">" } else { " " }, label),
+                    Style::default().fg(theme.label),
+                ),
+                Span::styled(format!(" ◀ {} ▶ ", value), value_style),
+            ])
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    match &form.error {
+        Some(error) => lines.push(Line::from(Span::styled(
+            format!(" {}", error),
+            Style::default().fg(theme.error),
+        ))),
+        None => lines.push(Line::from(Span::styled(
+            format!(" Saves {}", form.config.file_name()),
+            Style::default().fg(theme.muted),
+        ))),
+    }
+    lines.push(Line::from(Span::styled(
+        " ↑↓ field  ←→ change  PgUp/PgDn ±10",
+        Style::default().fg(theme.muted),
+    )));
+    lines.push(Line::from(Span::styled(
+        " ENTER generate  ESC cancel",
+        Style::default().fg(theme.muted),
+    )));
+
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::bordered()
+            .title(" Generate Galaxy ")
+            .border_style(Style::default().fg(theme.border)),
     );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
 
-    frame.render_widget(title, center_area);
+/// Renders the list of galaxy files and a preview of the selected one
+fn render_galaxy_picker(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme();
+    let picker_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    let items: Vec<ListItem> = app
+        .galaxy_files
+        .iter()
+        .map(|file| {
+            // Only the file name, the directory is in the title
+            let name = Path::new(file)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| file.clone());
+            ListItem::new(name)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::bordered()
+                .title(format!(" Galaxies in {} ", app.galaxy_dir))
+                .border_style(Style::default().fg(theme.border)),
+        )
+        .highlight_style(highlight_style(&theme, app.accessible))
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, picker_layout[0], &mut app.galaxy_file_state);
+    app.click_areas.galaxy_files = picker_layout[0];
+
+    let preview_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Fill(1)])
+        .split(picker_layout[1]);
+
+    let Some((galaxy, errors)) = &app.galaxy_preview else {
+        let empty = Paragraph::new("  No galaxy file selected").block(
+            Block::bordered()
+                .title(" Preview ")
+                .border_style(Style::default().fg(theme.border)),
+        );
+        frame.render_widget(empty, picker_layout[1]);
+        return;
+    };
+
+    let count_line = |label: &str, value: usize| {
+        Line::from(vec![
+            Span::styled(format!("  {}: ", label), Style::default().fg(theme.label)),
+            Span::styled(
+                value.to_string(),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+        ])
+    };
+
+    let mut info = vec![
+        count_line("Planets", galaxy.planets.len()),
+        count_line("Explorers", galaxy.explorers.len()),
+        count_line("Links", galaxy.link_count()),
+    ];
+    // Only the editor's format is previewed, the orchestrator may still load other files
+    match errors.first() {
+        Some(error) => info.push(Line::from(Span::styled(
+            format!("  Not in the editor's format, first error at {}", error),
+            Style::default().fg(theme.warning),
+        ))),
+        None => info.push(Line::from(Span::styled(
+            "  In the editor's format",
+            Style::default().fg(theme.success),
+        ))),
+    }
+
+    let info = Paragraph::new(info).block(
+        Block::bordered()
+            .title(" Preview ")
+            .border_style(Style::default().fg(theme.border)),
+    );
+    frame.render_widget(info, preview_layout[0]);
+
+    let ids: Vec<u32> = galaxy.planets.iter().map(|p| p.id).collect();
+    galaxy_map::render_galaxy_map(
+        frame,
+        preview_layout[1],
+        "Topology",
+        &ids,
+        &galaxy.links(),
+        None,
+        &theme,
+    );
 }